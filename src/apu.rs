@@ -0,0 +1,738 @@
+use crate::state;
+
+use std::fs;
+
+struct Envelope {
+    initial_volume: u8,
+    increase: bool,
+    period: u8,
+    timer: u8,
+    volume: u8,
+}
+
+impl Envelope {
+    fn new_envelope() -> Envelope {
+        Envelope { initial_volume: 0, increase: false, period: 0, timer: 0, volume: 0 }
+    }
+
+    fn write_register(&mut self, data: u8) {
+        self.initial_volume = data >> 4;
+        self.increase = data & 0b1000 != 0;
+        self.period = data & 0b111;
+    }
+
+    fn save_state(&self, writer: &mut state::StateWriter) {
+        writer.write_u8(self.initial_volume);
+        writer.write_bool(self.increase);
+        writer.write_u8(self.period);
+        writer.write_u8(self.timer);
+        writer.write_u8(self.volume);
+    }
+
+    fn load_state(&mut self, reader: &mut state::StateReader) -> Result<(), String> {
+        self.initial_volume = reader.read_u8()?;
+        self.increase = reader.read_bool()?;
+        self.period = reader.read_u8()?;
+        self.timer = reader.read_u8()?;
+        self.volume = reader.read_u8()?;
+        Ok(())
+    }
+
+    fn dac_enabled(&self) -> bool {
+        // the DAC is off when the initial volume is 0 and the envelope decreases
+        self.initial_volume != 0 || self.increase
+    }
+
+    fn trigger(&mut self) {
+        self.volume = self.initial_volume;
+        self.timer = self.period;
+    }
+
+    fn clock(&mut self) {
+        if self.period == 0 {
+            return;
+        }
+        if self.timer > 0 {
+            self.timer -= 1;
+        }
+        if self.timer == 0 {
+            self.timer = self.period;
+            if self.increase && self.volume < 15 {
+                self.volume += 1;
+            } else if !self.increase && self.volume > 0 {
+                self.volume -= 1;
+            }
+        }
+    }
+}
+
+struct SquareChannel {
+    enabled: bool,
+    duty: u8,
+    duty_position: u8,
+    length_counter: u16,
+    length_enabled: bool,
+    frequency: u16,
+    frequency_timer: u16,
+    envelope: Envelope,
+    // frequency sweep, only used by channel 1
+    sweep_period: u8,
+    sweep_negate: bool,
+    sweep_shift: u8,
+    sweep_timer: u8,
+    sweep_enabled: bool,
+    shadow_frequency: u16,
+}
+
+impl SquareChannel {
+    const DUTY_PATTERNS: [[u8; 8]; 4] = [
+        [0, 0, 0, 0, 0, 0, 0, 1], // 12.5%
+        [1, 0, 0, 0, 0, 0, 0, 1], // 25%
+        [1, 0, 0, 0, 0, 1, 1, 1], // 50%
+        [0, 1, 1, 1, 1, 1, 1, 0], // 75%
+    ];
+
+    fn new_channel() -> SquareChannel {
+        SquareChannel {
+            enabled: false,
+            duty: 0,
+            duty_position: 0,
+            length_counter: 0,
+            length_enabled: false,
+            frequency: 0,
+            frequency_timer: 0,
+            envelope: Envelope::new_envelope(),
+            sweep_period: 0,
+            sweep_negate: false,
+            sweep_shift: 0,
+            sweep_timer: 0,
+            sweep_enabled: false,
+            shadow_frequency: 0,
+        }
+    }
+
+    fn save_state(&self, writer: &mut state::StateWriter) {
+        writer.write_bool(self.enabled);
+        writer.write_u8(self.duty);
+        writer.write_u8(self.duty_position);
+        writer.write_u16(self.length_counter);
+        writer.write_bool(self.length_enabled);
+        writer.write_u16(self.frequency);
+        writer.write_u16(self.frequency_timer);
+        self.envelope.save_state(writer);
+        writer.write_u8(self.sweep_period);
+        writer.write_bool(self.sweep_negate);
+        writer.write_u8(self.sweep_shift);
+        writer.write_u8(self.sweep_timer);
+        writer.write_bool(self.sweep_enabled);
+        writer.write_u16(self.shadow_frequency);
+    }
+
+    fn load_state(&mut self, reader: &mut state::StateReader) -> Result<(), String> {
+        self.enabled = reader.read_bool()?;
+        self.duty = reader.read_u8()? & 0b11;
+        self.duty_position = reader.read_u8()? & 0b111;
+        self.length_counter = reader.read_u16()?;
+        self.length_enabled = reader.read_bool()?;
+        self.frequency = reader.read_u16_at_most(2047, "square channel frequency")?;
+        self.frequency_timer = reader.read_u16()?;
+        self.envelope.load_state(reader)?;
+        self.sweep_period = reader.read_u8()?;
+        self.sweep_negate = reader.read_bool()?;
+        self.sweep_shift = reader.read_u8_at_most(7, "sweep shift")?;
+        self.sweep_timer = reader.read_u8()?;
+        self.sweep_enabled = reader.read_bool()?;
+        self.shadow_frequency = reader.read_u16_at_most(2047, "sweep frequency")?;
+        Ok(())
+    }
+
+    fn write_register(&mut self, register: u16, data: u8) {
+        // register is the index of the register in the channel, from NRx0 to NRx4
+        match register {
+            0 => {
+                self.sweep_period = (data >> 4) & 0b111;
+                self.sweep_negate = data & 0b1000 != 0;
+                self.sweep_shift = data & 0b111;
+            },
+            1 => {
+                self.duty = data >> 6;
+                self.length_counter = 64 - (data & 0b111111) as u16;
+            },
+            2 => {
+                self.envelope.write_register(data);
+                if !self.envelope.dac_enabled() {
+                    self.enabled = false;
+                }
+            },
+            3 => self.frequency = (self.frequency & 0x700) | data as u16,
+            _ => {
+                self.frequency = (self.frequency & 0xFF) | (((data & 0b111) as u16) << 8);
+                self.length_enabled = data & 0b1000000 != 0;
+                if data & 0b10000000 != 0 {
+                    self.trigger();
+                }
+            },
+        }
+    }
+
+    fn trigger(&mut self) {
+        self.enabled = self.envelope.dac_enabled();
+        if self.length_counter == 0 {
+            self.length_counter = 64;
+        }
+        self.frequency_timer = (2048 - self.frequency) * 4;
+        self.envelope.trigger();
+
+        self.shadow_frequency = self.frequency;
+        self.sweep_timer = if self.sweep_period == 0 { 8 } else { self.sweep_period };
+        self.sweep_enabled = self.sweep_period != 0 || self.sweep_shift != 0;
+        if self.sweep_shift != 0 {
+            self.compute_sweep_frequency(); // overflow check only
+        }
+    }
+
+    fn compute_sweep_frequency(&mut self) -> u16 {
+        let delta = self.shadow_frequency >> self.sweep_shift;
+        let new_frequency = if self.sweep_negate {
+            self.shadow_frequency - delta
+        } else {
+            self.shadow_frequency + delta
+        };
+        if new_frequency > 2047 {
+            self.enabled = false;
+        }
+        new_frequency
+    }
+
+    fn clock_sweep(&mut self) {
+        if self.sweep_timer > 0 {
+            self.sweep_timer -= 1;
+        }
+        if self.sweep_timer != 0 {
+            return;
+        }
+        self.sweep_timer = if self.sweep_period == 0 { 8 } else { self.sweep_period };
+        if self.sweep_enabled && self.sweep_period != 0 {
+            let new_frequency = self.compute_sweep_frequency();
+            if new_frequency <= 2047 && self.sweep_shift != 0 {
+                self.shadow_frequency = new_frequency;
+                self.frequency = new_frequency;
+                self.compute_sweep_frequency(); // second overflow check with the new frequency
+            }
+        }
+    }
+
+    fn clock_length(&mut self) {
+        if self.length_enabled && self.length_counter > 0 {
+            self.length_counter -= 1;
+            if self.length_counter == 0 {
+                self.enabled = false;
+            }
+        }
+    }
+
+    fn tick(&mut self) {
+        if self.frequency_timer > 0 {
+            self.frequency_timer -= 1;
+        }
+        if self.frequency_timer == 0 {
+            self.frequency_timer = (2048 - self.frequency) * 4;
+            self.duty_position = (self.duty_position + 1) % 8;
+        }
+    }
+
+    fn output(&self) -> u8 {
+        if !self.enabled {
+            return 0;
+        }
+        SquareChannel::DUTY_PATTERNS[self.duty as usize][self.duty_position as usize] * self.envelope.volume
+    }
+}
+
+struct WaveChannel {
+    enabled: bool,
+    dac_enabled: bool,
+    length_counter: u16,
+    length_enabled: bool,
+    volume_code: u8,
+    frequency: u16,
+    frequency_timer: u16,
+    position: u8,
+    wave_ram: [u8; 16],
+}
+
+impl WaveChannel {
+    fn new_channel() -> WaveChannel {
+        WaveChannel {
+            enabled: false,
+            dac_enabled: false,
+            length_counter: 0,
+            length_enabled: false,
+            volume_code: 0,
+            frequency: 0,
+            frequency_timer: 0,
+            position: 0,
+            wave_ram: [0; 16],
+        }
+    }
+
+    fn save_state(&self, writer: &mut state::StateWriter) {
+        writer.write_bool(self.enabled);
+        writer.write_bool(self.dac_enabled);
+        writer.write_u16(self.length_counter);
+        writer.write_bool(self.length_enabled);
+        writer.write_u8(self.volume_code);
+        writer.write_u16(self.frequency);
+        writer.write_u16(self.frequency_timer);
+        writer.write_u8(self.position);
+        writer.write_bytes(&self.wave_ram);
+    }
+
+    fn load_state(&mut self, reader: &mut state::StateReader) -> Result<(), String> {
+        self.enabled = reader.read_bool()?;
+        self.dac_enabled = reader.read_bool()?;
+        self.length_counter = reader.read_u16()?;
+        self.length_enabled = reader.read_bool()?;
+        self.volume_code = reader.read_u8()? & 0b11;
+        self.frequency = reader.read_u16_at_most(2047, "wave channel frequency")?;
+        self.frequency_timer = reader.read_u16()?;
+        self.position = reader.read_u8()? & 0x1F;
+        reader.read_bytes_into(&mut self.wave_ram)
+    }
+
+    fn write_register(&mut self, register: u16, data: u8) {
+        match register {
+            0 => {
+                self.dac_enabled = data & 0b10000000 != 0;
+                if !self.dac_enabled {
+                    self.enabled = false;
+                }
+            },
+            1 => self.length_counter = 256 - data as u16,
+            2 => self.volume_code = (data >> 5) & 0b11,
+            3 => self.frequency = (self.frequency & 0x700) | data as u16,
+            _ => {
+                self.frequency = (self.frequency & 0xFF) | (((data & 0b111) as u16) << 8);
+                self.length_enabled = data & 0b1000000 != 0;
+                if data & 0b10000000 != 0 {
+                    self.trigger();
+                }
+            },
+        }
+    }
+
+    fn trigger(&mut self) {
+        self.enabled = self.dac_enabled;
+        if self.length_counter == 0 {
+            self.length_counter = 256;
+        }
+        self.frequency_timer = (2048 - self.frequency) * 2;
+        self.position = 0;
+    }
+
+    fn clock_length(&mut self) {
+        if self.length_enabled && self.length_counter > 0 {
+            self.length_counter -= 1;
+            if self.length_counter == 0 {
+                self.enabled = false;
+            }
+        }
+    }
+
+    fn tick(&mut self) {
+        if self.frequency_timer > 0 {
+            self.frequency_timer -= 1;
+        }
+        if self.frequency_timer == 0 {
+            self.frequency_timer = (2048 - self.frequency) * 2;
+            self.position = (self.position + 1) % 32;
+        }
+    }
+
+    fn output(&self) -> u8 {
+        if !self.enabled {
+            return 0;
+        }
+        // each byte of wave RAM holds 2 samples, upper nibble first
+        let byte = self.wave_ram[(self.position / 2) as usize];
+        let sample = if self.position & 1 == 0 { byte >> 4 } else { byte & 0xF };
+        match self.volume_code {
+            0 => 0,
+            1 => sample,
+            2 => sample >> 1,
+            _ => sample >> 2,
+        }
+    }
+}
+
+struct NoiseChannel {
+    enabled: bool,
+    length_counter: u16,
+    length_enabled: bool,
+    envelope: Envelope,
+    clock_shift: u8,
+    width_mode: bool, // 7 bits LFSR when set, 15 bits otherwise
+    divisor_code: u8,
+    frequency_timer: u32,
+    lfsr: u16,
+}
+
+impl NoiseChannel {
+    const DIVISORS: [u32; 8] = [8, 16, 32, 48, 64, 80, 96, 112];
+
+    fn new_channel() -> NoiseChannel {
+        NoiseChannel {
+            enabled: false,
+            length_counter: 0,
+            length_enabled: false,
+            envelope: Envelope::new_envelope(),
+            clock_shift: 0,
+            width_mode: false,
+            divisor_code: 0,
+            frequency_timer: 0,
+            lfsr: 0x7FFF,
+        }
+    }
+
+    fn save_state(&self, writer: &mut state::StateWriter) {
+        writer.write_bool(self.enabled);
+        writer.write_u16(self.length_counter);
+        writer.write_bool(self.length_enabled);
+        self.envelope.save_state(writer);
+        writer.write_u8(self.clock_shift);
+        writer.write_bool(self.width_mode);
+        writer.write_u8(self.divisor_code);
+        writer.write_u32(self.frequency_timer);
+        writer.write_u16(self.lfsr);
+    }
+
+    fn load_state(&mut self, reader: &mut state::StateReader) -> Result<(), String> {
+        self.enabled = reader.read_bool()?;
+        self.length_counter = reader.read_u16()?;
+        self.length_enabled = reader.read_bool()?;
+        self.envelope.load_state(reader)?;
+        self.clock_shift = reader.read_u8_at_most(15, "noise clock shift")?;
+        self.width_mode = reader.read_bool()?;
+        self.divisor_code = reader.read_u8()? & 0b111;
+        self.frequency_timer = reader.read_u32()?;
+        self.lfsr = reader.read_u16()?;
+        Ok(())
+    }
+
+    fn write_register(&mut self, register: u16, data: u8) {
+        match register {
+            0 => {}, // unused NR40
+            1 => self.length_counter = 64 - (data & 0b111111) as u16,
+            2 => {
+                self.envelope.write_register(data);
+                if !self.envelope.dac_enabled() {
+                    self.enabled = false;
+                }
+            },
+            3 => {
+                self.clock_shift = data >> 4;
+                self.width_mode = data & 0b1000 != 0;
+                self.divisor_code = data & 0b111;
+            },
+            _ => {
+                self.length_enabled = data & 0b1000000 != 0;
+                if data & 0b10000000 != 0 {
+                    self.trigger();
+                }
+            },
+        }
+    }
+
+    fn period(&self) -> u32 {
+        NoiseChannel::DIVISORS[self.divisor_code as usize] << self.clock_shift
+    }
+
+    fn trigger(&mut self) {
+        self.enabled = self.envelope.dac_enabled();
+        if self.length_counter == 0 {
+            self.length_counter = 64;
+        }
+        self.frequency_timer = self.period();
+        self.envelope.trigger();
+        self.lfsr = 0x7FFF;
+    }
+
+    fn clock_length(&mut self) {
+        if self.length_enabled && self.length_counter > 0 {
+            self.length_counter -= 1;
+            if self.length_counter == 0 {
+                self.enabled = false;
+            }
+        }
+    }
+
+    fn tick(&mut self) {
+        if self.frequency_timer > 0 {
+            self.frequency_timer -= 1;
+        }
+        if self.frequency_timer == 0 {
+            self.frequency_timer = self.period();
+            let xor = (self.lfsr & 1) ^ ((self.lfsr >> 1) & 1);
+            self.lfsr = (self.lfsr >> 1) | (xor << 14);
+            if self.width_mode {
+                self.lfsr = (self.lfsr & !0b1000000) | (xor << 6);
+            }
+        }
+    }
+
+    fn output(&self) -> u8 {
+        if !self.enabled || self.lfsr & 1 == 1 {
+            return 0;
+        }
+        self.envelope.volume
+    }
+}
+
+pub struct APU {
+    square_1: SquareChannel,
+    square_2: SquareChannel,
+    wave: WaveChannel,
+    noise: NoiseChannel,
+    registers: [u8; 0x20], // raw values written to 0xFF10..=0xFF2F, used for reads
+    powered: bool,
+    frame_sequencer_step: u8,
+    frame_sequencer_clocks: u16,
+    sample_clocks: u32,
+    samples: Vec<f32>, // interleaved left and right samples
+}
+
+impl APU {
+    pub const SAMPLE_RATE: u32 = 44100;
+    const CLOCK_RATE: u32 = 4194304;
+    const FRAME_SEQUENCER_CLOCKS: u16 = 8192; // 512 Hz
+
+    const BASE: u16 = 0xFF10;
+    const MASTER_VOLUME: u16 = 0xFF24; // NR50
+    const CHANNEL_PANNING: u16 = 0xFF25; // NR51
+    const SOUND_ON_OFF: u16 = 0xFF26; // NR52
+    const WAVE_RAM: u16 = 0xFF30;
+
+    // bits that always read as 1, for 0xFF10..=0xFF2F
+    const READ_MASKS: [u8; 0x20] = [
+        0x80, 0x3F, 0x00, 0xFF, 0xBF, // NR10-NR14
+        0xFF, 0x3F, 0x00, 0xFF, 0xBF, // NR20-NR24
+        0x7F, 0xFF, 0x9F, 0xFF, 0xBF, // NR30-NR34
+        0xFF, 0xFF, 0x00, 0x00, 0xBF, // NR40-NR44
+        0x00, 0x00, 0x70, // NR50-NR52
+        0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, // unused
+    ];
+
+    pub fn new_apu() -> APU {
+        APU {
+            square_1: SquareChannel::new_channel(),
+            square_2: SquareChannel::new_channel(),
+            wave: WaveChannel::new_channel(),
+            noise: NoiseChannel::new_channel(),
+            registers: [0; 0x20],
+            powered: false,
+            frame_sequencer_step: 0,
+            frame_sequencer_clocks: 0,
+            sample_clocks: 0,
+            samples: Vec::new(),
+        }
+    }
+
+    pub fn save_state(&self, writer: &mut state::StateWriter) {
+        self.square_1.save_state(writer);
+        self.square_2.save_state(writer);
+        self.wave.save_state(writer);
+        self.noise.save_state(writer);
+        writer.write_bytes(&self.registers);
+        writer.write_bool(self.powered);
+        writer.write_u8(self.frame_sequencer_step);
+        writer.write_u16(self.frame_sequencer_clocks);
+        writer.write_u32(self.sample_clocks);
+    }
+
+    pub fn load_state(&mut self, reader: &mut state::StateReader) -> Result<(), String> {
+        self.square_1.load_state(reader)?;
+        self.square_2.load_state(reader)?;
+        self.wave.load_state(reader)?;
+        self.noise.load_state(reader)?;
+        reader.read_bytes_into(&mut self.registers)?;
+        self.powered = reader.read_bool()?;
+        self.frame_sequencer_step = reader.read_u8()? & 0b111;
+        self.frame_sequencer_clocks = reader.read_u16()?;
+        self.sample_clocks = reader.read_u32()?;
+        self.samples.clear(); // samples generated before the load must not be played
+        Ok(())
+    }
+
+    pub fn tick(&mut self) {
+        // execute a clock cycle of the APU
+        if self.powered {
+            self.frame_sequencer_clocks += 1;
+            if self.frame_sequencer_clocks == APU::FRAME_SEQUENCER_CLOCKS {
+                self.frame_sequencer_clocks = 0;
+                self.clock_frame_sequencer();
+            }
+            self.square_1.tick();
+            self.square_2.tick();
+            self.wave.tick();
+            self.noise.tick();
+        }
+
+        // downsample to the output sample rate
+        self.sample_clocks += APU::SAMPLE_RATE;
+        if self.sample_clocks >= APU::CLOCK_RATE {
+            self.sample_clocks -= APU::CLOCK_RATE;
+            self.mix_sample();
+        }
+    }
+
+    fn clock_frame_sequencer(&mut self) {
+        // length counters on even steps, sweep on steps 2 and 6, envelopes on step 7
+        if self.frame_sequencer_step & 1 == 0 {
+            self.square_1.clock_length();
+            self.square_2.clock_length();
+            self.wave.clock_length();
+            self.noise.clock_length();
+        }
+        if self.frame_sequencer_step == 2 || self.frame_sequencer_step == 6 {
+            self.square_1.clock_sweep();
+        }
+        if self.frame_sequencer_step == 7 {
+            self.square_1.envelope.clock();
+            self.square_2.envelope.clock();
+            self.noise.envelope.clock();
+        }
+        self.frame_sequencer_step = (self.frame_sequencer_step + 1) % 8;
+    }
+
+    fn dac_output(value: u8, dac_enabled: bool) -> f32 {
+        // convert a digital 0..=15 value to an analog -1.0..=1.0 value
+        if !dac_enabled {
+            return 0.0;
+        }
+        (value as f32) / 7.5 - 1.0
+    }
+
+    fn mix_sample(&mut self) {
+        if !self.powered {
+            self.samples.push(0.0);
+            self.samples.push(0.0);
+            return;
+        }
+
+        let outputs = [
+            APU::dac_output(self.square_1.output(), self.square_1.envelope.dac_enabled()),
+            APU::dac_output(self.square_2.output(), self.square_2.envelope.dac_enabled()),
+            APU::dac_output(self.wave.output(), self.wave.dac_enabled),
+            APU::dac_output(self.noise.output(), self.noise.envelope.dac_enabled()),
+        ];
+        let panning = self.registers[(APU::CHANNEL_PANNING - APU::BASE) as usize];
+        let volume = self.registers[(APU::MASTER_VOLUME - APU::BASE) as usize];
+
+        let mut left = 0.0;
+        let mut right = 0.0;
+        for (i, output) in outputs.iter().enumerate() {
+            if panning & (0b10000 << i) != 0 {
+                left += output;
+            }
+            if panning & (1 << i) != 0 {
+                right += output;
+            }
+        }
+        let left_volume = (((volume >> 4) & 0b111) + 1) as f32 / 8.0;
+        let right_volume = ((volume & 0b111) + 1) as f32 / 8.0;
+        self.samples.push(left / 4.0 * left_volume);
+        self.samples.push(right / 4.0 * right_volume);
+    }
+
+    pub fn take_samples(&mut self) -> Vec<f32> {
+        // hand the generated samples over to the frontend
+        std::mem::take(&mut self.samples)
+    }
+
+    pub fn sample_count(&self) -> usize {
+        self.samples.len()
+    }
+
+    fn channel_status(&self) -> u8 {
+        (self.square_1.enabled as u8)
+            | (self.square_2.enabled as u8) << 1
+            | (self.wave.enabled as u8) << 2
+            | (self.noise.enabled as u8) << 3
+    }
+
+    pub fn fetch_byte(&self, address: u16) -> u8 {
+        match address {
+            APU::SOUND_ON_OFF => ((self.powered as u8) << 7) | 0x70 | self.channel_status(),
+            0xFF10..=0xFF2F => {
+                let index = (address - APU::BASE) as usize;
+                self.registers[index] | APU::READ_MASKS[index]
+            },
+            0xFF30..=0xFF3F => self.wave.wave_ram[(address - APU::WAVE_RAM) as usize],
+            _ => panic!("Address {:#06x} is not an APU register !", address),
+        }
+    }
+
+    pub fn set_byte(&mut self, address: u16, data: u8) {
+        match address {
+            APU::SOUND_ON_OFF => {
+                let powered = data & 0b10000000 != 0;
+                if self.powered && !powered {
+                    self.power_off();
+                } else if !self.powered && powered {
+                    self.frame_sequencer_step = 0;
+                    self.frame_sequencer_clocks = 0;
+                }
+                self.powered = powered;
+            },
+            0xFF30..=0xFF3F => self.wave.wave_ram[(address - APU::WAVE_RAM) as usize] = data,
+            0xFF10..=0xFF2F => {
+                if !self.powered {
+                    return; // registers are read only while the APU is off
+                }
+                self.registers[(address - APU::BASE) as usize] = data;
+                match address {
+                    0xFF10..=0xFF14 => self.square_1.write_register(address - 0xFF10, data),
+                    0xFF15..=0xFF19 => self.square_2.write_register(address - 0xFF15, data),
+                    0xFF1A..=0xFF1E => self.wave.write_register(address - 0xFF1A, data),
+                    0xFF1F..=0xFF23 => self.noise.write_register(address - 0xFF1F, data),
+                    _ => {}, // NR50, NR51 and unused registers are only stored
+                }
+            },
+            _ => panic!("Address {:#06x} is not an APU register !", address),
+        }
+    }
+
+    fn power_off(&mut self) {
+        // turning the APU off clears all of its registers, wave RAM is kept
+        let wave_ram = self.wave.wave_ram;
+        self.square_1 = SquareChannel::new_channel();
+        self.square_2 = SquareChannel::new_channel();
+        self.wave = WaveChannel::new_channel();
+        self.wave.wave_ram = wave_ram;
+        self.noise = NoiseChannel::new_channel();
+        self.registers = [0; 0x20];
+    }
+}
+
+pub fn write_wav(filename: &str, samples: &[f32]) -> std::io::Result<()> {
+    // write interleaved stereo samples as a 16 bits PCM WAV file
+    let data_size = (samples.len() * 2) as u32;
+    let mut content: Vec<u8> = Vec::with_capacity(44 + data_size as usize);
+    content.extend_from_slice(b"RIFF");
+    content.extend_from_slice(&(36 + data_size).to_le_bytes());
+    content.extend_from_slice(b"WAVEfmt ");
+    content.extend_from_slice(&16u32.to_le_bytes()); // fmt chunk size
+    content.extend_from_slice(&1u16.to_le_bytes()); // PCM format
+    content.extend_from_slice(&2u16.to_le_bytes()); // channels
+    content.extend_from_slice(&APU::SAMPLE_RATE.to_le_bytes());
+    content.extend_from_slice(&(APU::SAMPLE_RATE * 4).to_le_bytes()); // byte rate
+    content.extend_from_slice(&4u16.to_le_bytes()); // block align
+    content.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    content.extend_from_slice(b"data");
+    content.extend_from_slice(&data_size.to_le_bytes());
+    for sample in samples {
+        let value = (sample.clamp(-1.0, 1.0) * (i16::MAX as f32)) as i16;
+        content.extend_from_slice(&value.to_le_bytes());
+    }
+    fs::write(filename, content)
+}
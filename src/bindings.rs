@@ -0,0 +1,92 @@
+use gbemulator::joypad::Button;
+
+use sdl2::controller;
+use sdl2::keyboard::Keycode;
+
+use std::fs;
+
+// bindings file format, one GameBoy button per line followed by the keys and gamepad buttons pressing it :
+//   a = A, pad:a
+//   start = Return, pad:start
+// keys use SDL key names, gamepad buttons SDL game controller names, and lines starting with # are comments
+// buttons missing from the file keep their default bindings
+
+pub struct Bindings {
+    keys: Vec<(Keycode, Button)>,
+    pad_buttons: Vec<(controller::Button, Button)>,
+}
+
+impl Bindings {
+    const BUTTON_NAMES: [(&'static str, Button); 8] = [
+        ("right", Button::Right), ("left", Button::Left), ("up", Button::Up), ("down", Button::Down),
+        ("a", Button::A), ("b", Button::B), ("select", Button::Select), ("start", Button::Start),
+    ];
+
+    const DEFAULT_BINDINGS: &'static str = "
+        right = Right, pad:dpright
+        left = Left, pad:dpleft
+        up = Up, pad:dpup
+        down = Down, pad:dpdown
+        a = A, pad:a
+        b = B, pad:b
+        select = Space, pad:back
+        start = Return, pad:start
+    ";
+
+    pub fn new_default_bindings() -> Bindings {
+        let mut bindings = Bindings { keys: Vec::new(), pad_buttons: Vec::new() };
+        bindings.parse(Bindings::DEFAULT_BINDINGS).expect("Invalid default bindings");
+        bindings
+    }
+
+    pub const DEFAULT_PATH: &'static str = "bindings.cfg"; // read from the working directory when present
+
+    pub fn from_file(path: &str) -> Result<Bindings, String> {
+        let text = fs::read_to_string(path).map_err(|err| format!("Could not read bindings file {} : {}", path, err))?;
+        let mut bindings = Bindings::new_default_bindings();
+        bindings.parse(&text).map_err(|err| format!("{} : {}", path, err))?;
+        Ok(bindings)
+    }
+
+    fn parse(&mut self, text: &str) -> Result<(), String> {
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (name, inputs) = match line.find('=') {
+                Some(index) => (line[..index].trim(), &line[index + 1..]),
+                None => return Err(format!("line {} : expected <button> = <inputs>", number + 1)),
+            };
+            let button = match Bindings::BUTTON_NAMES.iter().find(|(button_name, _)| button_name.eq_ignore_ascii_case(name)) {
+                Some((_, button)) => *button,
+                None => return Err(format!("line {} : unknown button {}", number + 1, name)),
+            };
+            // the line replaces the previous bindings of the button
+            self.keys.retain(|(_, bound)| *bound != button);
+            self.pad_buttons.retain(|(_, bound)| *bound != button);
+            for input in inputs.split(',').map(|input| input.trim()).filter(|input| !input.is_empty()) {
+                if let Some(pad_name) = input.strip_prefix("pad:") {
+                    match controller::Button::from_string(pad_name) {
+                        Some(pad_button) => self.pad_buttons.push((pad_button, button)),
+                        None => return Err(format!("line {} : unknown gamepad button {}", number + 1, pad_name)),
+                    }
+                } else {
+                    match Keycode::from_name(input) {
+                        Some(key) => self.keys.push((key, button)),
+                        None => return Err(format!("line {} : unknown key {}", number + 1, input)),
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn key_button(&self, key: Keycode) -> Option<Button> {
+        self.keys.iter().find(|(bound, _)| *bound == key).map(|(_, button)| *button)
+    }
+
+    pub fn pad_button(&self, pad_button: controller::Button) -> Option<Button> {
+        self.pad_buttons.iter().find(|(bound, _)| *bound == pad_button).map(|(_, button)| *button)
+    }
+}
@@ -1,23 +1,11 @@
-use std::fs;
-
-struct ROM {
-    cartridge: Vec<u8>,
-}
-
-impl ROM {
-    pub fn from_file(filename: &String) -> ROM {
-        ROM {
-            cartridge: match fs::read(filename) {
-                Err(err) => panic!("Could not read content of {} : {}", filename, err),
-                Ok(file) => file,
-            },
-        }
-    }
-
-    fn get_byte(&self, i: u16) -> u8 {
-        self.cartridge[i as usize]
-    }
-}
+use crate::apu;
+use crate::cartridge;
+use crate::gpu;
+use crate::interrupts;
+use crate::joypad;
+use crate::serial;
+use crate::state;
+use crate::timer;
 
 struct WorkingRam {
     //data: Box<[u8]>,
@@ -43,78 +31,277 @@ impl WorkingRam {
         let real_address = address - self.base;
         self.data[real_address as usize] = data;
     }
+
+    fn save_state(&self, writer: &mut state::StateWriter) {
+        writer.write_bytes(&self.data);
+    }
+
+    fn load_state(&mut self, reader: &mut state::StateReader) -> Result<(), String> {
+        reader.read_bytes_into(&mut self.data)
+    }
 }
 
 pub struct Bus {
-    rom: ROM,
+    cartridge: cartridge::Cartridge,
     vram: WorkingRam,
-    external_ram: WorkingRam,
     wram1: WorkingRam,
     wram2: WorkingRam,
     oam: WorkingRam,
     io: WorkingRam,
     high_ram: WorkingRam,
-    interrupt_enable_register: u8,
+    interrupts: interrupts::InterruptController,
+    joypad: joypad::Joypad,
+    gpu: Option<gpu::GPU>, // only taken out of the bus while it ticks, since it accesses memory through the bus
+    timer: timer::Timer,
+    serial: serial::Serial,
+    apu: apu::APU,
+    boot_rom: Vec<u8>,
+    boot_rom_mapped: bool, // the boot ROM hides the start of the cartridge until 0xFF50 is written
+    clock_cycles: u64, // since power on
+    frame_completed: bool,
 }
 
 impl Bus {
-    pub fn new_bus(filename: &String) -> Bus {
-        Bus {
-            rom: ROM::from_file(filename),
+    const BOOT_ROM_SIZE: usize = 0x100;
+    const BOOT_ROM_DISABLE: u16 = 0xFF50;
+    const CLOCKS_PER_M_CYCLE: u8 = 4;
+
+    // IO registers as left by the DMG boot ROM ; NR52 comes first since the APU ignores writes while it is off
+    // the trigger bit of NRx4 is not set again, the boot sound is over when the cartridge starts
+    const POST_BOOT_IO: [(u16, u8); 26] = [
+        (0xFF26, 0xF1), (0xFF00, 0xCF), (0xFF02, 0x7E), (0xFF0F, 0xE1),
+        (0xFF10, 0x80), (0xFF11, 0xBF), (0xFF12, 0xF3), (0xFF13, 0xFF), (0xFF14, 0x3F),
+        (0xFF16, 0x3F), (0xFF18, 0xFF), (0xFF19, 0x3F),
+        (0xFF1A, 0x7F), (0xFF1B, 0xFF), (0xFF1C, 0x9F), (0xFF1D, 0xFF), (0xFF1E, 0x3F),
+        (0xFF20, 0xFF), (0xFF23, 0x3F), (0xFF24, 0x77), (0xFF25, 0xF3),
+        (0xFF40, 0x91), (0xFF41, 0x85), (0xFF47, 0xFC), (0xFF48, 0xFF), (0xFF49, 0xFF),
+    ];
+
+    pub fn new_bus(filename: &str) -> Result<Bus, String> {
+        Ok(Bus::from_cartridge(cartridge::Cartridge::from_file(filename)?))
+    }
+
+    pub fn from_cartridge(cartridge: cartridge::Cartridge) -> Bus {
+        // the bus starts in the state left by the boot ROM, see load_boot_rom to run it instead
+        let mut bus = Bus {
+            cartridge,
             vram: WorkingRam::from_size(8192, 0x8000),
-            external_ram: WorkingRam::from_size(8192, 0xA000),
             wram1: WorkingRam::from_size(4096, 0xC000),
             wram2: WorkingRam::from_size(4096, 0xD000),
             oam: WorkingRam::from_size(160, 0xFE00),
             io: WorkingRam::from_size(128, 0xFF00),
             high_ram: WorkingRam::from_size(127, 0xFF80),
-            interrupt_enable_register: 0,
+            interrupts: interrupts::InterruptController::new_interrupt_controller(),
+            joypad: joypad::Joypad::new_joypad(),
+            gpu: Some(gpu::GPU::new_gpu()),
+            timer: timer::Timer::new_timer(),
+            serial: serial::Serial::new_serial(),
+            apu: apu::APU::new_apu(),
+            boot_rom: Vec::new(),
+            boot_rom_mapped: false,
+            clock_cycles: 0,
+            frame_completed: false,
+        };
+        bus.timer = timer::Timer::new_post_boot_timer();
+        for (address, data) in Bus::POST_BOOT_IO.iter() {
+            bus.set_byte(*address, *data);
+        }
+        bus
+    }
+
+    pub fn load_boot_rom(&mut self, boot_rom: Vec<u8>) -> Result<(), String> {
+        // map a DMG boot ROM over the start of the cartridge and put the IO registers back in their power on state
+        if boot_rom.len() != Bus::BOOT_ROM_SIZE {
+            return Err(format!("Boot ROM must be {} bytes, got {} bytes", Bus::BOOT_ROM_SIZE, boot_rom.len()));
+        }
+        self.boot_rom = boot_rom;
+        self.boot_rom_mapped = true;
+        self.io = WorkingRam::from_size(128, 0xFF00);
+        self.interrupts = interrupts::InterruptController::new_interrupt_controller();
+        self.joypad = joypad::Joypad::new_joypad();
+        self.serial.reset();
+        self.timer = timer::Timer::new_timer();
+        self.apu = apu::APU::new_apu();
+        self.gpu = Some(gpu::GPU::new_gpu());
+        Ok(())
+    }
+
+    pub fn cartridge(&self) -> &cartridge::Cartridge {
+        &self.cartridge
+    }
+
+    pub fn cartridge_mut(&mut self) -> &mut cartridge::Cartridge {
+        &mut self.cartridge
+    }
+
+    pub fn save_state(&self, writer: &mut state::StateWriter) {
+        self.cartridge.save_state(writer);
+        for ram in [&self.vram, &self.wram1, &self.wram2, &self.oam, &self.io, &self.high_ram].iter() {
+            ram.save_state(writer);
+        }
+        self.interrupts.save_state(writer);
+        self.joypad.save_state(writer);
+        self.timer.save_state(writer);
+        self.serial.save_state(writer);
+        self.apu.save_state(writer);
+        writer.write_bool(self.boot_rom_mapped);
+    }
+
+    pub fn load_state(&mut self, reader: &mut state::StateReader) -> Result<(), String> {
+        self.cartridge.load_state(reader)?;
+        for ram in [&mut self.vram, &mut self.wram1, &mut self.wram2, &mut self.oam, &mut self.io, &mut self.high_ram].iter_mut() {
+            ram.load_state(reader)?;
         }
+        self.interrupts.load_state(reader)?;
+        self.joypad.load_state(reader)?;
+        self.timer.load_state(reader)?;
+        self.serial.load_state(reader)?;
+        self.apu.load_state(reader)?;
+        self.boot_rom_mapped = reader.read_bool()?;
+        if self.boot_rom_mapped && self.boot_rom.is_empty() {
+            return Err(String::from("Save state was made while running a boot ROM, which is not loaded"));
+        }
+        Ok(())
+    }
+
+    pub fn gpu(&self) -> &gpu::GPU {
+        self.gpu.as_ref().expect("The GPU is ticking")
+    }
+
+    pub fn gpu_mut(&mut self) -> &mut gpu::GPU {
+        self.gpu.as_mut().expect("The GPU is ticking")
+    }
+
+    pub fn clock_cycles(&self) -> u64 {
+        self.clock_cycles
+    }
+
+    pub fn take_frame_completed(&mut self) -> bool {
+        // true once after the GPU completed a frame
+        let frame_completed = self.frame_completed;
+        self.frame_completed = false;
+        frame_completed
+    }
+
+    pub fn tick_m_cycle(&mut self) {
+        // execute the 4 clocks of an M-cycle on the devices living on the bus
+        let mut gpu = self.gpu.take().expect("The GPU is already ticking");
+        for _ in 0..Bus::CLOCKS_PER_M_CYCLE {
+            if gpu.tick(self) {
+                self.frame_completed = true;
+            }
+            if self.timer.tick() {
+                self.request_interrupt(interrupts::InterruptController::TIMER);
+            }
+            if self.serial.tick(self.timer.internal_counter()) {
+                self.request_interrupt(interrupts::InterruptController::SERIAL);
+            }
+            self.apu.tick();
+            self.cartridge.tick();
+        }
+        self.gpu = Some(gpu);
+        self.clock_cycles += Bus::CLOCKS_PER_M_CYCLE as u64;
+    }
+
+    pub fn joypad(&self) -> &joypad::Joypad {
+        &self.joypad
+    }
+
+    pub fn press(&mut self, button: joypad::Button) {
+        if self.joypad.press(button) {
+            self.request_interrupt(interrupts::InterruptController::JOYPAD);
+        }
+    }
+
+    pub fn release(&mut self, button: joypad::Button) {
+        self.joypad.release(button);
+    }
+
+    pub fn connect_link(&mut self, endpoint: Box<dyn serial::LinkEndpoint>) {
+        self.serial.connect(endpoint);
+    }
+
+    pub fn take_audio_samples(&mut self) -> Vec<f32> {
+        self.apu.take_samples()
+    }
+
+    pub fn audio_sample_count(&self) -> usize {
+        self.apu.sample_count()
+    }
+
+    pub fn request_interrupt(&mut self, interrupt: u8) {
+        self.interrupts.request(interrupt);
+    }
+
+    pub fn interrupts(&self) -> &interrupts::InterruptController {
+        &self.interrupts
+    }
+
+    pub fn interrupts_mut(&mut self) -> &mut interrupts::InterruptController {
+        &mut self.interrupts
     }
 
     pub fn fetch_byte(&self, address: u16) -> u8 {
         match address {
-            /*0x0000..=0x3FFF => self.rom.get_byte(address),
-            0x4000..=0x7FFF => 0, // ROM bank 1..N in cartridge*/
-            0x0000..=0x7FFF => self.rom.get_byte(address),
+            0x0000..=0x00FF if self.boot_rom_mapped => self.boot_rom[address as usize],
+            0x0000..=0x7FFF => self.cartridge.fetch_byte(address), // ROM bank 0 and switchable ROM bank
             0x8000..=0x9FFF => self.vram.get_byte(address),
-            0xA000..=0xBFFF => self.external_ram.get_byte(address),
+            0xA000..=0xBFFF => self.cartridge.fetch_byte(address),
             0xC000..=0xCFFF => self.wram1.get_byte(address),
             0xD000..=0xDFFF => self.wram2.get_byte(address),
-            0xE000..=0xFDFF => self.wram1.get_byte(address),
+            0xE000..=0xFDFF => self.fetch_byte(address - 0x2000), // echo RAM mirrors 0xC000-0xDDFF
             0xFE00..=0xFE9F => self.oam.get_byte(address),
             0xFEA0..=0xFEFF => 0, //panic!("Address {:#x} is not usable !", address),
-            0xFF00..=0xFF7F => self.io.get_byte(address),
+            joypad::Joypad::REGISTER => self.joypad.fetch_byte(),
+            serial::Serial::TRANSFER_DATA | serial::Serial::TRANSFER_CONTROL => self.serial.fetch_byte(address),
+            0xFF04..=0xFF07 => self.timer.fetch_byte(address),
+            0xFF10..=0xFF3F => self.apu.fetch_byte(address),
+            interrupts::InterruptController::FLAG_REGISTER => self.interrupts.fetch_byte(address),
+            Bus::BOOT_ROM_DISABLE => 0xFF,
+            0xFF01..=0xFF7F => self.io.get_byte(address),
             0xFF80..=0xFFFE => self.high_ram.get_byte(address),
-            0xFFFF => self.interrupt_enable_register,
+            0xFFFF => self.interrupts.fetch_byte(address),
         }
     }
 
     pub fn fetch_word(&self, address: u16) -> u16 {
         let lower = self.fetch_byte(address);
-        let higher = self.fetch_byte(address + 1);
+        let higher = self.fetch_byte(address.wrapping_add(1));
         ((higher as u16) << 8) + (lower as u16)
     }
 
     pub fn set_byte(&mut self, address: u16, data: u8) {
         match address {
-            0x0000..=0x3FFF => {},
-            0x4000..=0x7FFF => panic!("ROM banks not supported !"), // ROM bank 1..N in cartridge
+            0x0000..=0x7FFF => self.cartridge.set_byte(address, data), // MBC registers
             0x8000..=0x9FFF => self.vram.set_byte(address, data),
-            0xA000..=0xBFFF => self.external_ram.set_byte(address, data),
+            0xA000..=0xBFFF => self.cartridge.set_byte(address, data),
             0xC000..=0xCFFF => self.wram1.set_byte(address, data),
             0xD000..=0xDFFF => self.wram2.set_byte(address, data),
-            0xE000..=0xFDFF => self.wram1.set_byte(address, data),
+            0xE000..=0xFDFF => self.set_byte(address - 0x2000, data),
             0xFE00..=0xFE9F => self.oam.set_byte(address, data),
             0xFEA0..=0xFEFF => {}, //panic!("Address {:#x} is not usable !", address),
-            0xFF00..=0xFF7F => self.io.set_byte(address, data),
+            joypad::Joypad::REGISTER => {
+                if self.joypad.set_byte(data) {
+                    self.request_interrupt(interrupts::InterruptController::JOYPAD);
+                }
+            },
+            serial::Serial::TRANSFER_DATA | serial::Serial::TRANSFER_CONTROL => self.serial.set_byte(address, data),
+            0xFF04..=0xFF07 => self.timer.set_byte(address, data),
+            0xFF10..=0xFF3F => self.apu.set_byte(address, data),
+            interrupts::InterruptController::FLAG_REGISTER => self.interrupts.set_byte(address, data),
+            Bus::BOOT_ROM_DISABLE => {
+                if data != 0 {
+                    self.boot_rom_mapped = false; // can't be mapped back until the next power on
+                }
+            },
+            0xFF01..=0xFF7F => self.io.set_byte(address, data),
             0xFF80..=0xFFFE => self.high_ram.set_byte(address, data),
-            0xFFFF => self.interrupt_enable_register = data,
+            0xFFFF => self.interrupts.set_byte(address, data),
         }
     }
 
     pub fn set_word(&mut self, address: u16, data: u16) {
         self.set_byte(address, (data & 0xFF) as u8);
-        self.set_byte(address + 1, ((data & 0xFF00) >> 8) as u8);
+        self.set_byte(address.wrapping_add(1), ((data & 0xFF00) >> 8) as u8);
     }
 }
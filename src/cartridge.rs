@@ -0,0 +1,298 @@
+use crate::header;
+use crate::rtc;
+use crate::state;
+
+use std::fs;
+
+enum Mbc {
+    RomOnly,
+    Mbc1,
+    Mbc2,
+    Mbc3,
+    Mbc5,
+}
+
+pub struct Cartridge {
+    header: header::CartridgeHeader,
+    rom: Vec<u8>,
+    ram: Vec<u8>,
+    mbc: Mbc,
+    rom_bank: u16, // for MBC1, only the 5 lower bits of the bank number
+    ram_bank: u8, // for MBC1, secondary 2 bits register used for RAM bank or upper ROM bank bits
+    ram_enabled: bool,
+    banking_mode: bool, // MBC1 only ; false is simple ROM banking, true is advanced banking
+    ram_modified: bool, // external RAM was written since the last save
+    rtc: Option<rtc::RTC>, // MBC3 real time clock
+}
+
+impl Cartridge {
+    const ROM_BANK_SIZE: usize = 0x4000;
+    const RAM_BANK_SIZE: usize = 0x2000;
+    const MBC2_RAM_SIZE: usize = 512;
+
+    pub fn from_file(filename: &str) -> Result<Cartridge, String> {
+        let rom = match fs::read(filename) {
+            Err(err) => return Err(format!("Could not read content of {} : {}", filename, err)),
+            Ok(file) => file,
+        };
+        Cartridge::from_rom(rom)
+    }
+
+    pub fn from_rom(mut rom: Vec<u8>) -> Result<Cartridge, String> {
+        let header = header::CartridgeHeader::parse(&rom)?;
+        if rom.len() < 0x8000 {
+            rom.resize(0x8000, 0xFF); // smallest cartridge is 2 ROM banks
+        }
+
+        let mbc = match header.cartridge_type {
+            0x00 | 0x08 | 0x09 => Mbc::RomOnly,
+            0x01..=0x03 => Mbc::Mbc1,
+            0x05 | 0x06 => Mbc::Mbc2,
+            0x0F..=0x13 => Mbc::Mbc3,
+            0x19..=0x1E => Mbc::Mbc5,
+            t => return Err(format!("Cartridge type {:#04x} not supported !", t)),
+        };
+
+        let ram_size = match mbc {
+            Mbc::Mbc2 => Cartridge::MBC2_RAM_SIZE, // 512 half bytes built in the MBC
+            _ => header.ram_size,
+        };
+
+        let rtc = match header.cartridge_type {
+            0x0F | 0x10 => Some(rtc::RTC::new_rtc()), // MBC3+TIMER
+            _ => None,
+        };
+
+        Ok(Cartridge {
+            header,
+            rom,
+            ram: vec![0; ram_size],
+            mbc,
+            rom_bank: 1,
+            ram_bank: 0,
+            ram_enabled: false,
+            banking_mode: false,
+            ram_modified: false,
+            rtc,
+        })
+    }
+
+    pub fn header(&self) -> &header::CartridgeHeader {
+        &self.header
+    }
+
+    pub fn ram(&self) -> &[u8] {
+        &self.ram
+    }
+
+    pub fn load_ram(&mut self, data: &[u8]) {
+        // restore external RAM from a save ; extra or missing bytes are ignored
+        let length = data.len().min(self.ram.len());
+        self.ram[..length].copy_from_slice(&data[..length]);
+        self.ram_modified = false;
+    }
+
+    pub fn save_data(&mut self) -> Vec<u8> {
+        // battery save content : external RAM, followed by the RTC state if there is a clock
+        let mut data = self.ram.clone();
+        if let Some(rtc) = &mut self.rtc {
+            data.append(&mut rtc.footer());
+        }
+        data
+    }
+
+    pub fn load_save_data(&mut self, data: &[u8]) {
+        if let Some(rtc) = &mut self.rtc {
+            if data.len() >= self.ram.len() + rtc::RTC::FOOTER_SIZE {
+                rtc.load_footer(&data[self.ram.len()..]);
+            }
+        }
+        self.load_ram(data);
+    }
+
+    pub fn save_state(&self, writer: &mut state::StateWriter) {
+        writer.write_bytes(&self.ram);
+        writer.write_u16(self.rom_bank);
+        writer.write_u8(self.ram_bank);
+        writer.write_bool(self.ram_enabled);
+        writer.write_bool(self.banking_mode);
+        if let Some(rtc) = &self.rtc {
+            rtc.save_state(writer);
+        }
+    }
+
+    pub fn load_state(&mut self, reader: &mut state::StateReader) -> Result<(), String> {
+        reader.read_bytes_into(&mut self.ram)?;
+        self.rom_bank = reader.read_u16()?;
+        self.ram_bank = reader.read_u8()?;
+        self.ram_enabled = reader.read_bool()?;
+        self.banking_mode = reader.read_bool()?;
+        if let Some(rtc) = &mut self.rtc {
+            rtc.load_state(reader)?;
+        }
+        self.ram_modified = true; // the loaded RAM may differ from the battery save
+        Ok(())
+    }
+
+    pub fn set_deterministic_rtc(&mut self, deterministic: bool) {
+        if let Some(rtc) = &mut self.rtc {
+            rtc.set_deterministic(deterministic);
+        }
+    }
+
+    pub fn tick(&mut self) {
+        if let Some(rtc) = &mut self.rtc {
+            rtc.tick();
+        }
+    }
+
+    pub fn ram_modified(&self) -> bool {
+        self.ram_modified
+    }
+
+    pub fn clear_ram_modified(&mut self) {
+        self.ram_modified = false;
+    }
+
+    fn rom_bank_count(&self) -> usize {
+        self.rom.len() / Cartridge::ROM_BANK_SIZE
+    }
+
+    fn ram_bank_count(&self) -> usize {
+        self.ram.len().div_ceil(Cartridge::RAM_BANK_SIZE)
+    }
+
+    fn get_rom_byte(&self, bank: usize, address: u16) -> u8 {
+        // bank numbers wrap around the actual ROM size, which is always a power of 2
+        let bank = bank % self.rom_bank_count();
+        let offset = (address as usize) & (Cartridge::ROM_BANK_SIZE - 1);
+        self.rom[bank * Cartridge::ROM_BANK_SIZE + offset]
+    }
+
+    fn ram_offset(&self, address: u16) -> Option<usize> {
+        // position in external RAM of the given 0xA000..=0xBFFF address, if any RAM is mapped
+        // ROM+RAM cartridges have no MBC to enable their RAM, it is always accessible
+        let ram_enabled = self.ram_enabled || matches!(self.mbc, Mbc::RomOnly);
+        if !ram_enabled || self.ram.is_empty() {
+            return None;
+        }
+        let offset = (address - 0xA000) as usize;
+        match self.mbc {
+            Mbc::Mbc2 => Some(offset % Cartridge::MBC2_RAM_SIZE), // only 9 bits of address are used
+            Mbc::Mbc1 => {
+                let bank = if self.banking_mode { self.ram_bank as usize } else { 0 };
+                let bank = bank % self.ram_bank_count();
+                Some((bank * Cartridge::RAM_BANK_SIZE + offset) % self.ram.len())
+            },
+            Mbc::Mbc3 if self.ram_bank > 0x03 => None, // RTC registers are selected
+            _ => {
+                let bank = (self.ram_bank as usize) % self.ram_bank_count();
+                Some((bank * Cartridge::RAM_BANK_SIZE + offset) % self.ram.len())
+            },
+        }
+    }
+
+    pub fn fetch_byte(&self, address: u16) -> u8 {
+        match address {
+            0x0000..=0x3FFF => {
+                let bank = match self.mbc {
+                    Mbc::Mbc1 if self.banking_mode => (self.ram_bank as usize) << 5,
+                    _ => 0,
+                };
+                self.get_rom_byte(bank, address)
+            },
+            0x4000..=0x7FFF => {
+                let bank = match self.mbc {
+                    Mbc::RomOnly => 1,
+                    Mbc::Mbc1 => ((self.ram_bank as usize) << 5) | (self.rom_bank as usize),
+                    _ => self.rom_bank as usize,
+                };
+                self.get_rom_byte(bank, address)
+            },
+            0xA000..=0xBFFF => match self.ram_offset(address) {
+                None => match &self.rtc {
+                    Some(rtc) if self.ram_enabled && rtc::RTC::is_register(self.ram_bank) => rtc.fetch_register(self.ram_bank),
+                    _ => 0xFF,
+                },
+                Some(offset) => match self.mbc {
+                    Mbc::Mbc2 => self.ram[offset] | 0xF0, // upper 4 bits are undefined and read as 1
+                    _ => self.ram[offset],
+                },
+            },
+            _ => panic!("Address {:#06x} is not mapped to the cartridge !", address),
+        }
+    }
+
+    pub fn set_byte(&mut self, address: u16, data: u8) {
+        match address {
+            0x0000..=0x7FFF => self.write_register(address, data),
+            0xA000..=0xBFFF => {
+                if let Some(offset) = self.ram_offset(address) {
+                    self.ram[offset] = match self.mbc {
+                        Mbc::Mbc2 => data & 0xF,
+                        _ => data,
+                    };
+                    self.ram_modified = true;
+                } else if let Some(rtc) = &mut self.rtc {
+                    if self.ram_enabled && rtc::RTC::is_register(self.ram_bank) {
+                        rtc.set_register(self.ram_bank, data);
+                        self.ram_modified = true;
+                    }
+                }
+            },
+            _ => panic!("Address {:#06x} is not mapped to the cartridge !", address),
+        }
+    }
+
+    fn write_register(&mut self, address: u16, data: u8) {
+        // writes to the ROM area are intercepted by the MBC to select banks
+        match self.mbc {
+            Mbc::RomOnly => {},
+            Mbc::Mbc1 => match address {
+                0x0000..=0x1FFF => self.ram_enabled = data & 0xF == 0xA,
+                0x2000..=0x3FFF => {
+                    self.rom_bank = (data & 0b11111) as u16;
+                    if self.rom_bank == 0 {
+                        self.rom_bank = 1; // bank 0 can't be mapped at 0x4000, bank 1 is used instead
+                    }
+                },
+                0x4000..=0x5FFF => self.ram_bank = data & 0b11,
+                _ => self.banking_mode = data & 1 == 1,
+            },
+            Mbc::Mbc2 => {
+                if address <= 0x3FFF {
+                    if address & 0x100 == 0 { // bit 8 of the address selects the register
+                        self.ram_enabled = data & 0xF == 0xA;
+                    } else {
+                        self.rom_bank = (data & 0xF) as u16;
+                        if self.rom_bank == 0 {
+                            self.rom_bank = 1;
+                        }
+                    }
+                }
+            },
+            Mbc::Mbc3 => match address {
+                0x0000..=0x1FFF => self.ram_enabled = data & 0xF == 0xA,
+                0x2000..=0x3FFF => {
+                    self.rom_bank = (data & 0x7F) as u16;
+                    if self.rom_bank == 0 {
+                        self.rom_bank = 1;
+                    }
+                },
+                0x4000..=0x5FFF => self.ram_bank = data, // 0x08 to 0x0C select RTC registers
+                _ => {
+                    if let Some(rtc) = &mut self.rtc {
+                        rtc.write_latch(data);
+                    }
+                },
+            },
+            Mbc::Mbc5 => match address {
+                0x0000..=0x1FFF => self.ram_enabled = data & 0xF == 0xA,
+                0x2000..=0x2FFF => self.rom_bank = (self.rom_bank & 0x100) | (data as u16),
+                0x3000..=0x3FFF => self.rom_bank = (self.rom_bank & 0xFF) | (((data & 1) as u16) << 8),
+                0x4000..=0x5FFF => self.ram_bank = data & 0xF,
+                _ => {},
+            },
+        }
+    }
+}
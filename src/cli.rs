@@ -0,0 +1,140 @@
+pub struct Options {
+    pub rom_path: String,
+    pub scale: f32,
+    pub debug: bool,
+    pub breakpoints: Vec<u16>,
+    pub boot_rom: Option<String>,
+    pub headless: bool,
+    pub frames: Option<u32>,
+    pub wav_path: Option<String>,
+    pub mute: bool,
+    pub save_dir: Option<String>,
+    pub link: Option<String>,
+    pub link_host: Option<u16>,
+    pub link_join: Option<String>,
+    pub bindings: Option<String>,
+}
+
+pub fn print_usage() {
+    println!("Usage : GBEmulator [options] <rom>");
+    println!("Options :");
+    println!("  --scale <n>        window scale factor (default 2)");
+    println!("  --debug            start with the debugger enabled");
+    println!("  --break <addr>     add a debugger breakpoint at addr, implies --debug");
+    println!("  --boot-rom <path>  run the given DMG boot ROM before the cartridge");
+    println!("  --headless         run without opening a window");
+    println!("  --frames <n>       stop after n frames (required with --headless)");
+    println!("  --wav <path>       write the audio output of a headless run to a WAV file");
+    println!("  --mute             disable audio output");
+    println!("  --save-dir <path>  directory for battery saves (default: next to the ROM)");
+    println!("  --link <endpoint>  plug the link cable into stdout (prints what is sent) or loopback");
+    println!("  --host <port>      wait for another emulator to join on this port, connected by a link cable");
+    println!("  --join <address>   connect the link cable to an emulator started with --host, at address or port");
+    println!("  --bindings <path>  read the keyboard and gamepad bindings from a file (default: bindings.cfg when present)");
+    println!("  -h, --help         print this message");
+    println!("Addresses can be written in either decimal or hexadecimal format with a 0x prefix");
+}
+
+fn parse_address(value: &str) -> Result<u16, String> {
+    let result = match value.strip_prefix("0x") {
+        Some(hex) => u16::from_str_radix(hex, 16),
+        None => value.parse::<u16>(),
+    };
+    result.map_err(|_| format!("Invalid address : {}", value))
+}
+
+fn next_value(args: &mut std::vec::IntoIter<String>, option: &str) -> Result<String, String> {
+    args.next().ok_or(format!("Missing value for option {}", option))
+}
+
+pub fn parse_args(args: Vec<String>) -> Result<Option<Options>, String> {
+    // returns None if only the help message was requested
+    let mut options = Options {
+        rom_path: String::new(),
+        scale: 2.0,
+        debug: false,
+        breakpoints: Vec::new(),
+        boot_rom: None,
+        headless: false,
+        frames: None,
+        wav_path: None,
+        mute: false,
+        save_dir: None,
+        link: None,
+        link_host: None,
+        link_join: None,
+        bindings: None,
+    };
+    let mut rom_path = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--scale" => {
+                let value = next_value(&mut args, &arg)?;
+                options.scale = match value.parse::<f32>() {
+                    Ok(scale) if scale >= 1.0 => scale,
+                    _ => return Err(format!("Invalid scale : {}", value)),
+                };
+            },
+            "--debug" => options.debug = true,
+            "--break" => {
+                let value = next_value(&mut args, &arg)?;
+                options.breakpoints.push(parse_address(&value)?);
+                options.debug = true;
+            },
+            "--boot-rom" => options.boot_rom = Some(next_value(&mut args, &arg)?),
+            "--headless" => options.headless = true,
+            "--frames" => {
+                let value = next_value(&mut args, &arg)?;
+                options.frames = match value.parse::<u32>() {
+                    Ok(frames) => Some(frames),
+                    Err(_) => return Err(format!("Invalid frame count : {}", value)),
+                };
+            },
+            "--wav" => options.wav_path = Some(next_value(&mut args, &arg)?),
+            "--mute" => options.mute = true,
+            "--save-dir" => options.save_dir = Some(next_value(&mut args, &arg)?),
+            "--link" => {
+                let value = next_value(&mut args, &arg)?;
+                if value != "stdout" && value != "loopback" {
+                    return Err(format!("Invalid link endpoint : {}", value));
+                }
+                options.link = Some(value);
+            },
+            "--host" => {
+                let value = next_value(&mut args, &arg)?;
+                options.link_host = match value.parse::<u16>() {
+                    Ok(port) => Some(port),
+                    Err(_) => return Err(format!("Invalid port : {}", value)),
+                };
+            },
+            "--join" => options.link_join = Some(next_value(&mut args, &arg)?),
+            "--bindings" => options.bindings = Some(next_value(&mut args, &arg)?),
+            _ if arg.starts_with('-') => return Err(format!("Unknown option : {}", arg)),
+            _ => {
+                if rom_path.is_some() {
+                    return Err(format!("Unexpected argument : {}", arg));
+                }
+                rom_path = Some(arg);
+            },
+        }
+    }
+
+    options.rom_path = rom_path.ok_or("No ROM file given")?;
+    if options.headless && options.frames.is_none() {
+        return Err(String::from("--headless requires --frames <n>"));
+    }
+    if options.headless && options.debug {
+        return Err(String::from("The debugger is not available in headless mode"));
+    }
+    let links = [options.link.is_some(), options.link_host.is_some(), options.link_join.is_some()];
+    if links.iter().filter(|&&link| link).count() > 1 {
+        return Err(String::from("Only one of --link, --host and --join can be used"));
+    }
+    if options.wav_path.is_some() && !options.headless {
+        return Err(String::from("--wav is only available in headless mode"));
+    }
+    Ok(Some(options))
+}
@@ -1,6 +1,8 @@
 use crate::bus;
 use crate::instructions;
 use crate::instructions2;
+use crate::interrupts;
+use crate::state;
 
 pub struct Register {
     pub low: u8,
@@ -34,6 +36,20 @@ impl Register {
     }
 }
 
+pub enum CPUEvent {
+    IllegalOpcode { opcode: u8, address: u16 }, // the CPU locked up
+}
+
+impl CPUEvent {
+    pub fn describe(&self) -> String {
+        match self {
+            CPUEvent::IllegalOpcode { opcode, address } => {
+                format!("Illegal opcode {:#04x} at {:#06x}, the CPU is locked up", opcode, address)
+            },
+        }
+    }
+}
+
 pub struct CPU {
     pub af: Register,
     pub bc: Register,
@@ -41,42 +57,124 @@ pub struct CPU {
     pub hl: Register,
     pub sp: u16,
     pub pc: u16,
-    clock_cycles_to_go: u8,
+    m_cycles: u8, // M-cycles spent so far by the current instruction
     pub stopped: bool,
     pub halted: bool,
+    pub halt_bug: bool, // the next opcode fetch does not increment PC
     pub ime: bool,
+    pub ime_scheduled: bool, // EI enables interrupts only after the next instruction
+    pub locked_up: bool, // an illegal opcode was executed, nothing but a power cycle resumes execution
+    event: Option<CPUEvent>, // to be taken by the frontend
 }
 
 impl CPU {
-    pub fn new_cpu() -> CPU {
+    pub fn new_cpu(header_checksum: u8) -> CPU {
+        // registers as left by the DMG boot ROM, which sets the H and C flags unless the header checksum is 0
+        let flags = if header_checksum == 0 { 0x80 } else { 0xB0 };
+        CPU {
+            af: Register { high: 0x01, low: flags },
+            bc: Register { high: 0x00, low: 0x13 },
+            de: Register { high: 0x00, low: 0xD8 },
+            hl: Register { high: 0x01, low: 0x4D },
+            sp: 0xFFFE,
+            pc: 0x100,
+            m_cycles: 0,
+            stopped: false,
+            halted: false,
+            halt_bug: false,
+            ime: false,
+            ime_scheduled: false,
+            locked_up: false,
+            event: None,
+        }
+    }
+
+    pub fn new_cpu_at_power_on() -> CPU {
+        // registers at power on, before the boot ROM runs from address 0
         CPU {
             af: Register::new_register(),
             bc: Register::new_register(),
             de: Register::new_register(),
             hl: Register::new_register(),
             sp: 0,
-            pc: 0x100,
-            clock_cycles_to_go: 0,
+            pc: 0,
+            m_cycles: 0,
             stopped: false,
             halted: false,
+            halt_bug: false,
             ime: false,
+            ime_scheduled: false,
+            locked_up: false,
+            event: None,
         }
     }
 
-    pub fn tick(&mut self, bus: &mut bus::Bus) {
-        // execute a tick of the CPU
-        /*let timer = bus.fetch_byte(0xFF04); // timer register to be incremented
-        bus.set_byte(0xFF04, timer.wrapping_add(1));*/
-
-        if self.clock_cycles_to_go > 0 {
-            self.clock_cycles_to_go -= 1;
+    pub fn step(&mut self, bus: &mut bus::Bus) {
+        // execute the next instruction, the rest of the system runs during each of its M-cycles
+        if self.locked_up {
+            // interrupts are not serviced either, but the other devices keep running
+            bus.tick_m_cycle();
+            return;
+        }
+        if self.halted {
+            // no instruction is fetched until an enabled interrupt is requested, even if IME is off
+            if !self.interrupt_pending(bus) {
+                bus.tick_m_cycle();
+                return;
+            }
+            self.halted = false;
+        }
+        self.m_cycles = 0;
+        // interrupts are checked between instructions
+        if self.ime && self.interrupt_pending(bus) {
+            self.dispatch_interrupt(bus);
         } else {
             self.execute_instruction(bus);
         }
     }
 
-    pub fn get_clock_cycles(&self) -> u8 {
-        self.clock_cycles_to_go
+    pub fn interrupt_pending(&self, bus: &bus::Bus) -> bool {
+        bus.interrupts().pending() != 0
+    }
+
+    pub fn save_state(&self, writer: &mut state::StateWriter) {
+        for register in [&self.af, &self.bc, &self.de, &self.hl].iter() {
+            writer.write_u8(register.high);
+            writer.write_u8(register.low);
+        }
+        writer.write_u16(self.sp);
+        writer.write_u16(self.pc);
+        writer.write_bool(self.stopped);
+        writer.write_bool(self.halted);
+        writer.write_bool(self.halt_bug);
+        writer.write_bool(self.ime);
+        writer.write_bool(self.ime_scheduled);
+        writer.write_bool(self.locked_up);
+    }
+
+    pub fn load_state(&mut self, reader: &mut state::StateReader) -> Result<(), String> {
+        for register in [&mut self.af, &mut self.bc, &mut self.de, &mut self.hl].iter_mut() {
+            register.high = reader.read_u8()?;
+            register.low = reader.read_u8()?;
+        }
+        self.sp = reader.read_u16()?;
+        self.pc = reader.read_u16()?;
+        self.stopped = reader.read_bool()?;
+        self.halted = reader.read_bool()?;
+        self.halt_bug = reader.read_bool()?;
+        self.ime = reader.read_bool()?;
+        self.ime_scheduled = reader.read_bool()?;
+        self.locked_up = reader.read_bool()?;
+        Ok(())
+    }
+
+    pub fn lock_up(&mut self, opcode: u8, address: u16) {
+        self.locked_up = true;
+        self.event = Some(CPUEvent::IllegalOpcode { opcode, address });
+    }
+
+    pub fn take_event(&mut self) -> Option<CPUEvent> {
+        self.event.take()
     }
 
     pub fn extract_flag(&self, c: char) -> bool {
@@ -122,64 +220,105 @@ impl CPU {
 
     fn execute_instruction(&mut self, bus: &mut bus::Bus) {
         // fetch instruction byte on bus based on pc register
-        let op = bus.fetch_byte(self.pc);
+        let enable_interrupts = self.ime_scheduled;
+        let op = if self.halt_bug {
+            self.halt_bug = false; // the byte after HALT is read twice
+            self.read_byte(bus, self.pc)
+        } else {
+            self.fetch_d8(bus)
+        };
         let current_instruction = match op {
-            0xCB => &instructions2::Instruction::SECOND_SET[bus.fetch_byte(self.pc + 1) as usize],
+            0xCB => &instructions2::Instruction::SECOND_SET[self.fetch_d8(bus) as usize],
             _ => &instructions::Instruction::SET[op as usize],
         };
 
-        // identify instruction and execute it
-        self.pc += 1;
-        let previous_pc = self.pc;
-        (current_instruction.execute)(self, bus);
-        if previous_pc == self.pc {
-            // TEMPORARY ; CAN POTENTIALLY CAUSE BUGS IF JUMP OF 0 (although nonsensical)
-            //if a jump did not occurr
-            self.pc += current_instruction.op_len - 1;
+        // execute it, the handler fetches its own operands
+        let cycles = (current_instruction.execute)(self, bus);
+        // the M-cycles that did not access memory are spent at the end of the instruction
+        while self.m_cycles < cycles {
+            self.internal_cycle(bus);
         }
-        self.clock_cycles_to_go += current_instruction.clock_cycles;
-
-        // interrupts
-        if self.ime == true {
-            let enabled = bus.fetch_byte(0xFFFF);
-            let requested = bus.fetch_byte(0xFF0F);
-            if enabled & 1 == 1 && requested & 1 == 1 { // if vblank interrupt is enabled and requested
-                self.ime = false;
-                bus.set_byte(0xFF0F, requested ^ 1);
-                self.push_stack(bus, self.pc);
-                self.pc = 0x40;
-            } else if enabled & 0b10 == 0b10 && requested & 0b10 == 0b10 { // LCD STAT interrupt
-                self.ime = false;
-                bus.set_byte(0xFF0F, requested ^ 0b10);
-                self.push_stack(bus, self.pc);
-                self.pc = 0x48;
-            } else if enabled & 0b100 == 0b100 && requested & 0b100 == 0b100 { // Timer interrupt
-                self.ime = false;
-                bus.set_byte(0xFF0F, requested ^ 0b100);
-                self.push_stack(bus, self.pc);
-                self.pc = 0x50;
-            } else if enabled & 0b1000 == 0b1000 && requested & 0b1000 == 0b1000 { // serial interrupt
-                self.ime = false;
-                bus.set_byte(0xFF0F, requested ^ 0b1000);
-                self.push_stack(bus, self.pc);
-                self.pc = 0x58;
-            } else if enabled & 0b10000 == 0b10000 && requested & 0b10000 == 0b10000 { // joypad interrupt
-                self.ime = false;
-                bus.set_byte(0xFF0F, requested ^ 0b10000);
-                self.push_stack(bus, self.pc);
-                self.pc = 0x60;
-            }
+
+        if enable_interrupts && self.ime_scheduled {
+            // the instruction following EI has completed, unless it was DI
+            self.ime = true;
+            self.ime_scheduled = false;
+        }
+    }
+
+    fn dispatch_interrupt(&mut self, bus: &mut bus::Bus) {
+        // push PC and jump to the handler of the highest priority interrupt
+        self.ime = false;
+        if self.halt_bug {
+            // EI followed by HALT with an interrupt pending : the handler returns to the HALT instruction
+            self.halt_bug = false;
+            self.pc = self.pc.wrapping_sub(1);
+        }
+        // 5 M-cycles : 2 wait cycles, the 2 pushes and the jump
+        self.internal_cycle(bus);
+        self.internal_cycle(bus);
+        self.sp = self.sp.wrapping_sub(1);
+        self.write_byte(bus, self.sp, (self.pc >> 8) as u8);
+        // the interrupt is chosen between the two pushes, if the upper byte overwrote IE the dispatch can be cancelled
+        let pending = bus.interrupts().pending();
+        self.sp = self.sp.wrapping_sub(1);
+        self.write_byte(bus, self.sp, (self.pc & 0xFF) as u8);
+        match interrupts::InterruptController::highest_priority(pending) {
+            Some((interrupt, address)) => {
+                bus.interrupts_mut().acknowledge(interrupt);
+                self.pc = address;
+            },
+            None => self.pc = 0x0000,
         }
+        self.internal_cycle(bus);
+    }
+
+    pub fn read_byte(&mut self, bus: &mut bus::Bus, address: u16) -> u8 {
+        // every memory access of the CPU takes an M-cycle, during which the rest of the system runs
+        let data = bus.fetch_byte(address);
+        self.internal_cycle(bus);
+        data
+    }
+
+    pub fn write_byte(&mut self, bus: &mut bus::Bus, address: u16, data: u8) {
+        bus.set_byte(address, data);
+        self.internal_cycle(bus);
+    }
+
+    pub fn fetch_d8(&mut self, bus: &mut bus::Bus) -> u8 {
+        // read the byte at PC and move past it
+        let data = self.read_byte(bus, self.pc);
+        self.pc = self.pc.wrapping_add(1);
+        data
+    }
+
+    pub fn fetch_d16(&mut self, bus: &mut bus::Bus) -> u16 {
+        // immediate words are little endian
+        let low = self.fetch_d8(bus);
+        let high = self.fetch_d8(bus);
+        ((high as u16) << 8) + (low as u16)
+    }
+
+    pub fn internal_cycle(&mut self, bus: &mut bus::Bus) {
+        // an M-cycle without memory access
+        bus.tick_m_cycle();
+        self.m_cycles += 1;
     }
 
     pub fn push_stack(&mut self, bus: &mut bus::Bus, data: u16) {
-        self.sp -= 2;
-        bus.set_word(self.sp, data);
+        // SP is decremented during an internal cycle, then the high byte is written first
+        self.internal_cycle(bus);
+        self.sp = self.sp.wrapping_sub(1);
+        self.write_byte(bus, self.sp, (data >> 8) as u8);
+        self.sp = self.sp.wrapping_sub(1);
+        self.write_byte(bus, self.sp, (data & 0xFF) as u8);
     }
 
     pub fn pop_stack_d16(&mut self, bus: &mut bus::Bus) -> u16 {
-        let data = bus.fetch_word(self.sp);
-        self.sp += 2;
-        data
+        let low = self.read_byte(bus, self.sp);
+        self.sp = self.sp.wrapping_add(1);
+        let high = self.read_byte(bus, self.sp);
+        self.sp = self.sp.wrapping_add(1);
+        ((high as u16) << 8) + (low as u16)
     }
 }
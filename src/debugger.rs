@@ -1,8 +1,8 @@
-use crate::bus;
-use crate::cpu;
-use crate::gpu;
-use crate::instructions;
-use crate::instructions2;
+use gbemulator::bus;
+use gbemulator::cpu;
+use gbemulator::instructions;
+use gbemulator::instructions2;
+use gbemulator::GameBoy;
 
 use std::env;
 
@@ -16,6 +16,7 @@ enum CommandType {
     Dump,
     ValueBp,
     Print,
+    Info,
     Help,
     Invalid,
 }
@@ -62,7 +63,7 @@ impl Debugger {
         ret
     }
 
-    fn add_breakpoint(&mut self, bp: u16) -> i16 {
+    pub fn add_breakpoint(&mut self, bp: u16) -> i16 {
         if self.is_a_breakpoint(bp) {
             return -1; // don't allow multiple breakpoints at same address
         }
@@ -95,6 +96,7 @@ impl Debugger {
         println!("Commands :");
         println!("b: breakpoint manipulation");
         println!("p: print cpu state");
+        println!("i: print cartridge header information");
         println!("v: add value based breakpoint of a register");
         println!("c: continue running");
         println!("d: dump memory");
@@ -228,6 +230,8 @@ impl Debugger {
             };
             println!("{:#x} : {}", op, current_instruction.disassembly);
             Debugger::dump_registers(cpu, bus);
+        } else if command.name == CommandType::Info {
+            println!("{}", bus.cartridge().header().describe());
         } else if command.name == CommandType::Step {
             if self.stepping == false {
                 println!("Entering step mode");
@@ -251,6 +255,7 @@ impl Debugger {
             "d" => CommandType::Dump,
             "v" => CommandType::ValueBp,
             "p" => CommandType::Print,
+            "i" => CommandType::Info,
             "s" => CommandType::Step,
             _ => { println!("Invalid command : {}", tokens[0]); CommandType::Invalid },
         };
@@ -258,17 +263,13 @@ impl Debugger {
         return ret;
     }
 
-    fn tick_devices(
-        &self,
-        cpu: &mut cpu::CPU,
-        bus: &mut bus::Bus,
-        gpu: &mut gpu::GPU,
-        keys: &mut crate::Keys,
-        canvas: &mut sdl2::render::Canvas<sdl2::video::Window>,
-    ) {
-        keys.update_register(bus);
-        gpu.tick(bus, canvas);
-        cpu.tick(bus);
+    fn tick_devices(&mut self, gameboy: &mut GameBoy) -> bool {
+        let frame_completed = gameboy.step();
+        if let Some(event) = gameboy.take_event() {
+            println!("{}", event.describe());
+            self.paused = true;
+        }
+        frame_completed
     }
 
     fn handle_command(&mut self, bus: &bus::Bus, cpu: &cpu::CPU) -> CommandType {
@@ -291,7 +292,9 @@ impl Debugger {
         com.name
     }
 
-    pub fn tick(&mut self, cpu: &mut cpu::CPU, bus: &mut bus::Bus, gpu: &mut gpu::GPU, keys: &mut crate::Keys, canvas: &mut sdl2::render::Canvas<sdl2::video::Window>) {
+    pub fn tick(&mut self, gameboy: &mut GameBoy) -> bool {
+        // returns true if a frame was completed during this tick
+        let cpu = &gameboy.cpu;
         //check for breakpoints
         if self.is_a_breakpoint(cpu.pc) {
             if self.paused == false {
@@ -306,19 +309,17 @@ impl Debugger {
         }
         //if stopped
         if self.paused == false && self.stepping == false {
-            self.tick_devices(cpu, bus, gpu, keys, canvas);
+            self.tick_devices(gameboy)
         }
         //else
         else {
-            if cpu.get_clock_cycles() == 0 {
-                let com = self.handle_command(bus, cpu);
+            // a tick runs a whole instruction, so the CPU is always between instructions here
+            let com = self.handle_command(&gameboy.bus, &gameboy.cpu);
 
-                if com == CommandType::Step || com == CommandType::Continue {
-                    self.tick_devices(cpu, bus, gpu, keys, canvas);
-                }
-            } else {
-                self.tick_devices(cpu, bus, gpu, keys, canvas);
+            if com == CommandType::Step || com == CommandType::Continue {
+                return self.tick_devices(gameboy);
             }
+            false
         }
     }
 }
@@ -0,0 +1,200 @@
+use crate::bus;
+use crate::cartridge;
+use crate::cpu;
+use crate::header;
+use crate::joypad;
+use crate::save;
+use crate::serial;
+use crate::state;
+
+use std::path::PathBuf;
+
+pub struct GameBoy {
+    pub cpu: cpu::CPU,
+    pub bus: bus::Bus,
+    save_path: Option<PathBuf>,
+    frames_since_save: u32,
+}
+
+impl GameBoy {
+    const CLOCKS_PER_FRAME: u32 = 70224; // 154 lines of 456 clocks
+    const AUTOSAVE_FRAMES: u32 = 300; // about 5 seconds
+
+    const STATE_MAGIC: &'static [u8; 4] = b"GBES";
+    const STATE_VERSION: u32 = 10; // to be incremented whenever the layout of the state changes
+
+    pub fn new_gameboy(filename: &str) -> Result<GameBoy, String> {
+        Ok(GameBoy::from_cartridge(cartridge::Cartridge::from_file(filename)?))
+    }
+
+    pub fn from_rom(rom: Vec<u8>) -> Result<GameBoy, String> {
+        Ok(GameBoy::from_cartridge(cartridge::Cartridge::from_rom(rom)?))
+    }
+
+    fn from_cartridge(cartridge: cartridge::Cartridge) -> GameBoy {
+        GameBoy {
+            cpu: cpu::CPU::new_cpu(cartridge.header().header_checksum),
+            bus: bus::Bus::from_cartridge(cartridge),
+            save_path: None,
+            frames_since_save: 0,
+        }
+    }
+
+    pub fn load_boot_rom(&mut self, boot_rom: Vec<u8>) -> Result<(), String> {
+        // start from the power on state and run the boot ROM before the cartridge ; to be called before running
+        self.bus.load_boot_rom(boot_rom)?;
+        self.cpu = cpu::CPU::new_cpu_at_power_on();
+        Ok(())
+    }
+
+    pub fn load_battery_save(&mut self, path: PathBuf) -> Result<(), String> {
+        // bind the battery backed RAM to a save file, loading it if it already exists
+        if !self.header().has_battery() {
+            return Ok(());
+        }
+        if let Some(data) = save::read_save(&path)? {
+            self.bus.cartridge_mut().load_save_data(&data);
+        }
+        self.save_path = Some(path);
+        Ok(())
+    }
+
+    pub fn flush_battery_save(&mut self) -> Result<(), String> {
+        // write external RAM to the save file if it changed
+        self.frames_since_save = 0;
+        let path = match &self.save_path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        if !self.bus.cartridge().ram_modified() {
+            return Ok(());
+        }
+        let data = self.bus.cartridge_mut().save_data();
+        save::write_save(path, &data)?;
+        self.bus.cartridge_mut().clear_ram_modified();
+        Ok(())
+    }
+
+    pub fn connect_link(&mut self, endpoint: Box<dyn serial::LinkEndpoint>) {
+        // plug something on the other end of the link cable, nothing is connected by default
+        self.bus.connect_link(endpoint);
+    }
+
+    pub fn set_deterministic_rtc(&mut self, deterministic: bool) {
+        // make the cartridge clock advance with emulated time only, for reproducible runs
+        self.bus.cartridge_mut().set_deterministic_rtc(deterministic);
+    }
+
+    pub fn autosave(&mut self) -> Result<(), String> {
+        // to be called once per frame, flushes the save every few seconds
+        self.frames_since_save += 1;
+        if self.frames_since_save < GameBoy::AUTOSAVE_FRAMES {
+            return Ok(());
+        }
+        self.flush_battery_save()
+    }
+
+    pub fn save_state(&self) -> Vec<u8> {
+        // snapshot of the whole machine, tied to the loaded ROM
+        let mut writer = state::StateWriter::new_writer();
+        for byte in GameBoy::STATE_MAGIC.iter() {
+            writer.write_u8(*byte);
+        }
+        writer.write_u32(GameBoy::STATE_VERSION);
+        writer.write_u16(self.header().computed_global_checksum);
+        self.cpu.save_state(&mut writer);
+        self.bus.gpu().save_state(&mut writer);
+        self.bus.save_state(&mut writer);
+        writer.into_bytes()
+    }
+
+    pub fn load_state(&mut self, data: &[u8]) -> Result<(), String> {
+        // restore a snapshot made by save_state ; the machine is left untouched if it is invalid
+        let mut reader = state::StateReader::new_reader(data);
+        let mut magic = [0u8; 4];
+        for byte in magic.iter_mut() {
+            *byte = reader.read_u8()?;
+        }
+        if &magic != GameBoy::STATE_MAGIC {
+            return Err(String::from("Not a save state"));
+        }
+        let version = reader.read_u32()?;
+        if version != GameBoy::STATE_VERSION {
+            return Err(format!("Unsupported save state version {} (expected {})", version, GameBoy::STATE_VERSION));
+        }
+        if reader.read_u16()? != self.header().computed_global_checksum {
+            return Err(String::from("Save state was made with a different ROM"));
+        }
+
+        let backup = self.save_state();
+        let result = self.load_state_body(&mut reader);
+        if result.is_err() {
+            let mut backup_reader = state::StateReader::new_reader(&backup[10..]); // skip magic, version and checksum
+            self.load_state_body(&mut backup_reader).expect("Could not restore the state after a failed load");
+        }
+        result
+    }
+
+    fn load_state_body(&mut self, reader: &mut state::StateReader) -> Result<(), String> {
+        self.cpu.load_state(reader)?;
+        self.bus.gpu_mut().load_state(reader)?;
+        self.bus.load_state(reader)?;
+        if !reader.is_at_end() {
+            return Err(String::from("Save state has trailing data"));
+        }
+        Ok(())
+    }
+
+    pub fn step(&mut self) -> bool {
+        // execute an instruction, and the clocks of the whole machine it takes ; returns true when a frame was completed
+        if self.cpu.stopped {
+            // the system clock is stopped, nothing runs until a selected joypad line goes low
+            if !self.bus.joypad().any_line_low() {
+                return false;
+            }
+            self.cpu.stopped = false;
+        }
+        self.cpu.step(&mut self.bus);
+        self.bus.take_frame_completed()
+    }
+
+    pub fn run_frame(&mut self) {
+        // run until the GPU completes a frame, or for a frame worth of clocks if the LCD is off, or until STOP
+        let start = self.bus.clock_cycles();
+        while self.bus.clock_cycles() - start < GameBoy::CLOCKS_PER_FRAME as u64 {
+            if self.step() || self.cpu.stopped {
+                return;
+            }
+        }
+    }
+
+    pub fn take_event(&mut self) -> Option<cpu::CPUEvent> {
+        // something the user should know about happened in the CPU since the last call
+        self.cpu.take_event()
+    }
+
+    pub fn header(&self) -> &header::CartridgeHeader {
+        self.bus.cartridge().header()
+    }
+
+    pub fn framebuffer(&self) -> &[u8] {
+        // shade of each pixel of the last frame, from 0 (white) to 3 (black), see gpu::GPU::SHADE_COLORS
+        self.bus.gpu().framebuffer()
+    }
+
+    pub fn press(&mut self, button: joypad::Button) {
+        self.bus.press(button);
+    }
+
+    pub fn release(&mut self, button: joypad::Button) {
+        self.bus.release(button);
+    }
+
+    pub fn take_audio_samples(&mut self) -> Vec<f32> {
+        self.bus.take_audio_samples()
+    }
+
+    pub fn audio_sample_count(&self) -> usize {
+        self.bus.audio_sample_count()
+    }
+}
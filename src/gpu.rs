@@ -1,56 +1,142 @@
 use crate::bus;
+use crate::interrupts;
+use crate::state;
 
 pub struct GPU {
     clock_cycles: u16,
     current_line: u8,
     mode: u8,
     stopped: bool,
+    window_triggered: bool, // LY matched WY during this frame, the window can be drawn from now on
+    window_line: u8, // line of the window to draw next, only incremented on lines where it is drawn
+    window_full_line: bool, // WX was 166 on the previous line, the window covers this whole line
+    stat_line: bool, // the enabled STAT interrupt sources are ORed together, the interrupt is requested when this goes high
+    framebuffer: Vec<u8>, // shades of the screen pixels after the palettes, line by line
+    background_line: Vec<u8>, // color numbers of the background and window on the line being drawn, for sprite priority
 }
 
 impl GPU {
-    const SCREEN_WIDTH: u8 = 160;
-    const MAX_LINE: u8 = 143; // 144 lines in total
+    pub const SCREEN_WIDTH: u8 = 160;
+    pub const SCREEN_HEIGHT: u8 = 144;
+    const LINE_VBLANK_START: u8 = 144; // the first line after the 144 visible ones
     const LINE_VBLANK_END: u8 = 153;
 
     const TILESET_1: u16 = 0x8000;
-    const TILESET_2: u16 = 0x9000; // from -127 to 128, 0x9000 is pattern 0 but tileset starts at 0x8800
+    const TILESET_2: u16 = 0x9000; // signed tile numbers from -128 to 127, the tileset starts at 0x8800
     const BG_MAP_1: u16 = 0x9800;
-    //const BG_MAP_2: u16 = 0x9C00;
+    const BG_MAP_2: u16 = 0x9C00;
     const OAM: u16 = 0xFE00;
     const BG_PALETTE: u16 = 0xFF47;
+    const OBJ_PALETTE_0: u16 = 0xFF48;
+    const OBJ_PALETTE_1: u16 = 0xFF49;
     const CONTROL_REGISTER: u16 = 0xFF40;
     const STATUS_REGISTER: u16 = 0xFF41;
     const SCROLL_Y: u16 = 0xFF42;
     const SCROLL_X: u16 = 0xFF43;
     const Y_COORDINATE: u16 = 0xFF44;
-    //const Y_COMPARE: u16 = 0xFF45;
+    const Y_COMPARE: u16 = 0xFF45;
     const DMA_TRANSFER_REGISTER: u16 = 0xFF46;
+    const WINDOW_Y: u16 = 0xFF4A;
+    const WINDOW_X: u16 = 0xFF4B; // the window starts at WX - 7
+    const WINDOW_X_MAX: u8 = 166;
+
+    const SPRITES_NB: u16 = 40;
+    const MAX_SPRITES_PER_LINE: usize = 10;
 
     const OAM_ACCESS_SCANLINE_CLOCKS: u16 = 80;
     const VRAM_ACCESS_SCANLINE_CLOCKS: u16 = 172;
     const HORIZONTAL_BLANK_CLOCKS: u16 = 204;
     const VERTICAL_BLANCK_LINE_CLOCKS: u16 = 456; // single line of vlank ; 10 lines total
 
+    pub const SHADE_COLORS: [u32; 4] = [0xFFFFFF, 0xC0C0C0, 0x606060, 0x000000]; // 0xRRGGBB color of each shade
+    const WHITE: u8 = 0;
+
+    // STAT bits, the interrupt enable bits are written by the program and the others by the GPU
+    const STAT_COINCIDENCE: u8 = 0b100;
+    const STAT_HBLANK_INTERRUPT: u8 = 0b1000;
+    const STAT_VBLANK_INTERRUPT: u8 = 0b10000;
+    const STAT_OAM_INTERRUPT: u8 = 0b100000;
+    const STAT_COINCIDENCE_INTERRUPT: u8 = 0b1000000;
+    const STAT_INTERRUPT_ENABLES: u8 = 0b1111000;
+
     pub fn new_gpu() -> GPU {
-        GPU {clock_cycles: 0, current_line: 0, mode: 2, stopped: false,}
+        GPU {
+            clock_cycles: 0,
+            current_line: 0,
+            mode: 2,
+            stopped: false,
+            window_triggered: false,
+            window_line: 0,
+            window_full_line: false,
+            stat_line: false,
+            framebuffer: vec![GPU::WHITE; (GPU::SCREEN_WIDTH as usize) * (GPU::SCREEN_HEIGHT as usize)],
+            background_line: vec![0; GPU::SCREEN_WIDTH as usize],
+        }
     }
 
-    pub fn tick(&mut self, bus: &mut bus::Bus, canvas: &mut sdl2::render::Canvas<sdl2::video::Window>) {
+    pub fn save_state(&self, writer: &mut state::StateWriter) {
+        writer.write_u16(self.clock_cycles);
+        writer.write_u8(self.current_line);
+        writer.write_u8(self.mode);
+        writer.write_bool(self.stopped);
+        writer.write_bool(self.window_triggered);
+        writer.write_u8(self.window_line);
+        writer.write_bool(self.window_full_line);
+        writer.write_bool(self.stat_line);
+    }
+
+    pub fn load_state(&mut self, reader: &mut state::StateReader) -> Result<(), String> {
+        let clock_cycles = reader.read_u16()?;
+        let current_line = reader.read_u8_at_most(GPU::LINE_VBLANK_END, "GPU line")?;
+        let mode = reader.read_u8_at_most(3, "GPU mode")?;
+        // the mode must match the line, and its clock count must not be past its end
+        let mode_clocks = match mode {
+            0 => GPU::HORIZONTAL_BLANK_CLOCKS,
+            1 => GPU::VERTICAL_BLANCK_LINE_CLOCKS,
+            2 => GPU::OAM_ACCESS_SCANLINE_CLOCKS,
+            _ => GPU::VRAM_ACCESS_SCANLINE_CLOCKS,
+        };
+        if (mode == 1) != (current_line >= GPU::LINE_VBLANK_START) || clock_cycles >= mode_clocks {
+            return Err(format!("Save state has an invalid GPU position (mode {}, line {}, clock {})", mode, current_line, clock_cycles));
+        }
+        self.clock_cycles = clock_cycles;
+        self.current_line = current_line;
+        self.mode = mode;
+        self.stopped = reader.read_bool()?;
+        self.window_triggered = reader.read_bool()?;
+        self.window_line = reader.read_u8_at_most(GPU::SCREEN_HEIGHT, "window line")?;
+        self.window_full_line = reader.read_bool()?;
+        self.stat_line = reader.read_bool()?;
+        Ok(())
+    }
+
+    pub fn framebuffer(&self) -> &[u8] {
+        &self.framebuffer
+    }
+
+    pub fn tick(&mut self, bus: &mut bus::Bus) -> bool {
+        // execute a clock cycle of the GPU ; returns true when a full frame was just rendered
         let control_reg = bus.fetch_byte(GPU::CONTROL_REGISTER);
         let display_enable = control_reg & 0b10000000;
         if display_enable == 0 {
             if self.stopped == false {
-                canvas.set_draw_color(sdl2::pixels::Color::WHITE);
-                canvas.clear();
-                canvas.present();
+                for pixel in self.framebuffer.iter_mut() {
+                    *pixel = GPU::WHITE;
+                }
 
                 self.current_line = 0;
                 self.clock_cycles = 0;
                 self.mode = 0;
                 self.stopped = true;
-            } 
-            return;
+                self.reset_window();
+                self.stat_line = false;
+                self.update_status(bus);
+                return true; // show the blank screen
+            }
+            return false;
         }
+        self.stopped = false;
+        let mut frame_completed = false;
 
         // check if DMA transfer was started
         let dma = bus.fetch_byte(GPU::DMA_TRANSFER_REGISTER) as u16;
@@ -68,12 +154,10 @@ impl GPU {
                 if self.clock_cycles == GPU::HORIZONTAL_BLANK_CLOCKS { // hblank ends
                     self.clock_cycles = 0;
                     self.current_line += 1;
-                    if self.current_line == GPU::MAX_LINE { // beginning hblank of last line => vblank
+                    if self.current_line == GPU::LINE_VBLANK_START { // all the lines were drawn => vblank
                         self.mode = 1;
-                        let requested = bus.fetch_byte(0xFF0F);
-                        bus.set_byte(0xFF0F, requested | 1);
-                        // eventually render canvas here
-                        self.render_canvas(canvas);
+                        bus.request_interrupt(interrupts::InterruptController::VBLANK);
+                        frame_completed = true;
                     } else {
                         self.mode = 2; // hblank over, start scanning again
                     }
@@ -86,6 +170,7 @@ impl GPU {
                     if self.current_line > GPU::LINE_VBLANK_END { // ending vblank, resume scanning
                         self.mode = 2;
                         self.current_line = 0;
+                        self.reset_window();
                         // lock oam
                     }
                 }
@@ -102,140 +187,212 @@ impl GPU {
                 if self.clock_cycles == GPU::VRAM_ACCESS_SCANLINE_CLOCKS { // horizontal scanning ends
                     self.clock_cycles = 0;
                     self.mode = 0;
-                    // write scanline to canvas
-                    self.write_scanline(bus, canvas);
+                    // write scanline to framebuffer
+                    self.write_scanline(bus);
                 }
             },
             _ => panic!("Unknown GPU mode, aborting")
         }
 
-        // update io ports
+        self.update_status(bus);
+        frame_completed
+    }
+
+    fn update_status(&mut self, bus: &mut bus::Bus) {
+        // update LY and the GPU bits of STAT, and request the STAT interrupt if one of its enabled sources became active
         bus.set_byte(GPU::Y_COORDINATE, self.current_line);
-        bus.set_byte(GPU::STATUS_REGISTER, self.mode);
+        let status = bus.fetch_byte(GPU::STATUS_REGISTER) & GPU::STAT_INTERRUPT_ENABLES;
+        let coincidence = if self.current_line == bus.fetch_byte(GPU::Y_COMPARE) { GPU::STAT_COINCIDENCE } else { 0 };
+        bus.set_byte(GPU::STATUS_REGISTER, 0b10000000 | status | coincidence | self.mode); // bit 7 is unused and reads as 1
+        if self.stopped {
+            return; // no interrupt while the screen is off
+        }
+
+        let stat_line = (coincidence != 0 && status & GPU::STAT_COINCIDENCE_INTERRUPT != 0)
+            || (self.mode == 0 && status & GPU::STAT_HBLANK_INTERRUPT != 0)
+            || (self.mode == 1 && status & GPU::STAT_VBLANK_INTERRUPT != 0)
+            // the OAM interrupt also triggers at the start of vblank
+            || ((self.mode == 2 || (self.mode == 1 && self.current_line == GPU::LINE_VBLANK_START)) && status & GPU::STAT_OAM_INTERRUPT != 0);
+        // while a source keeps the line high, the other ones can not request the interrupt again
+        if stat_line && !self.stat_line {
+            bus.request_interrupt(interrupts::InterruptController::LCD_STAT);
+        }
+        self.stat_line = stat_line;
+    }
+
+    fn reset_window(&mut self) {
+        // at the start of a frame
+        self.window_triggered = false;
+        self.window_line = 0;
+        self.window_full_line = false;
+    }
+
+    fn set_pixel(&mut self, x: i32, color: u8) {
+        if x >= 0 && x < GPU::SCREEN_WIDTH as i32 {
+            let line_start = (self.current_line as usize) * (GPU::SCREEN_WIDTH as usize);
+            self.framebuffer[line_start + x as usize] = color;
+        }
     }
 
-    fn choose_color_from_palette(&self, bus: &bus::Bus, canvas: &mut sdl2::render::Canvas<sdl2::video::Window>, color_nb: u8) {
-        let palette = bus.fetch_byte(GPU::BG_PALETTE);
-        let shade = match color_nb {
+    fn set_background_pixel(&mut self, bus: &bus::Bus, x: u8, color_nb: u8) {
+        self.background_line[x as usize] = color_nb;
+        let color = self.choose_color_from_palette(bus, GPU::BG_PALETTE, color_nb);
+        self.set_pixel(x as i32, color);
+    }
+
+    fn choose_color_from_palette(&self, bus: &bus::Bus, palette_address: u16, color_nb: u8) -> u8 {
+        let palette = bus.fetch_byte(palette_address);
+        match color_nb {
             3 => (palette & 0b11000000) >> 6,
             2 => (palette & 0b110000) >> 4,
             1 => (palette & 0b1100) >> 2,
             0 => palette & 0b11,
             _ => panic!("Palette color_nb {} not valid !", color_nb),
-        };
+        }
+    }
 
-        match shade {
-            3 => canvas.set_draw_color(sdl2::pixels::Color::BLACK),
-            2 => canvas.set_draw_color(sdl2::pixels::Color::from((96, 96, 96))),
-            1 => canvas.set_draw_color(sdl2::pixels::Color::from((192, 192, 192))),
-            0 => canvas.set_draw_color(sdl2::pixels::Color::from((255, 255, 255, 0))),
-            _ => panic!("Shade {} not valid!", shade),
-        };
+    fn tile_data_address(control_reg: u8, nb_tile: u8) -> u16 {
+        // background and window tiles are numbered from 0x8000, or signed around 0x9000 if LCDC bit 4 is cleared
+        if control_reg & 0b10000 != 0 {
+            GPU::TILESET_1 + 16 * (nb_tile as u16)
+        } else {
+            GPU::TILESET_2.wrapping_add((16 * (nb_tile as i8 as i16)) as u16)
+        }
     }
 
-    fn render_background_line(&self, bus: &bus::Bus, canvas: &mut sdl2::render::Canvas<sdl2::video::Window>) {
-        let tileset = bus.fetch_byte(GPU::CONTROL_REGISTER) & 0b10000;
-        let scroll_x = bus.fetch_byte(GPU::SCROLL_X);
-        let scroll_y = bus.fetch_byte(GPU::SCROLL_Y);
+    fn fetch_tile_pixel(bus: &bus::Bus, tile_address: u16, x: u8, y: u8) -> u8 {
+        // color number of a pixel of a tile ; each row is 2 bytes, the first one holding the low bits of the colors
+        let row_address = tile_address + (y as u16) * 2;
+        let low = (bus.fetch_byte(row_address) >> (7 - x)) & 1;
+        let high = (bus.fetch_byte(row_address + 1) >> (7 - x)) & 1;
+        low + (high << 1)
+    }
+
+    fn fetch_map_pixel(bus: &bus::Bus, control_reg: u8, tile_map: u16, x: u8, y: u8) -> u8 {
+        // color number of a pixel of a 256x256 tile map
+        let nb_tile = bus.fetch_byte(tile_map + (y as u16 / 8) * 32 + (x as u16 / 8));
+        GPU::fetch_tile_pixel(bus, GPU::tile_data_address(control_reg, nb_tile), x % 8, y % 8)
+    }
 
-        let nb_line = (self.current_line.wrapping_add(scroll_y) / 8) % 32; // ith line of sprites
+    fn render_background_line(&mut self, bus: &bus::Bus) {
+        let control_reg = bus.fetch_byte(GPU::CONTROL_REGISTER);
+        let tile_map = if control_reg & 0b1000 == 0 { GPU::BG_MAP_1 } else { GPU::BG_MAP_2 };
+        let scroll_x = bus.fetch_byte(GPU::SCROLL_X);
+        let y = self.current_line.wrapping_add(bus.fetch_byte(GPU::SCROLL_Y)); // the map wraps around
 
         for i in 0..GPU::SCREEN_WIDTH {
-            let index = (((i as u8) + scroll_x) / 8) as u16;
-            let nb_sprite = bus.fetch_byte(GPU::BG_MAP_1 + ((nb_line as u16) * 32) + index);
-            let pos_x_in_sprite = ((i as u8) + scroll_x) % 8;
-            let pos_y_in_sprite = self.current_line.wrapping_add(scroll_y) % 8;
-            
-            // drawing the sprite
-            let shift = 0b10000000 >> pos_x_in_sprite;
-            let shade: u8 = if tileset == 0 {
-                let relative_address_offset = 16 * (nb_sprite as i16);
-                let raw_1 = bus.fetch_byte(((GPU::TILESET_2 as i16) + relative_address_offset + ((pos_y_in_sprite as i16) * 2)) as u16);
-                let raw_1 = (raw_1 & shift) >> (7 - pos_x_in_sprite);
-                let raw_2 = bus.fetch_byte(((GPU::TILESET_2 as i16) + relative_address_offset + ((pos_y_in_sprite as i16) * 2 + 1)) as u16);
-                let raw_2 = (raw_2 & shift) >> (7 - pos_x_in_sprite);
-                raw_1 + (raw_2 << 1)
-            } else {
-                let address_offset = 16 * (nb_sprite as u16);
-                let raw_1 = bus.fetch_byte(GPU::TILESET_1 + address_offset + ((pos_y_in_sprite as u16) * 2));
-                let raw_1 = (raw_1 & shift) >> (7 - pos_x_in_sprite);
-                let raw_2 = bus.fetch_byte(GPU::TILESET_1 + address_offset + ((pos_y_in_sprite as u16) * 2 + 1));
-                let raw_2 = (raw_2 & shift) >> (7 - pos_x_in_sprite);
-                raw_1 + (raw_2 << 1)
-            };
-
-            self.choose_color_from_palette(bus, canvas, shade);
-            canvas.draw_point(sdl2::rect::Point::new(i as i32, self.current_line as i32)).unwrap();
-        }
-    }
-
-    fn check_8x8_sprite_rendering(&self, x: i32, y: i32) -> bool {
-        ((self.current_line as u16 as i32) >= y && (self.current_line as u16 as i32) <= y + 7)
-        && (x > 0 && x < 168)
-    }
-
-    fn render_sprite_line(&self, bus: &bus::Bus, canvas: &mut sdl2::render::Canvas<sdl2::video::Window>) {
-        let sprite_size = GPU::CONTROL_REGISTER & 0b100;
-        for i in 0..=40 {
+            let color_nb = GPU::fetch_map_pixel(bus, control_reg, tile_map, i.wrapping_add(scroll_x), y);
+            self.set_background_pixel(bus, i, color_nb);
+        }
+    }
+
+    fn render_window_line(&mut self, bus: &bus::Bus) {
+        let control_reg = bus.fetch_byte(GPU::CONTROL_REGISTER);
+        let window_x = bus.fetch_byte(GPU::WINDOW_X);
+        if !self.window_triggered || (window_x > GPU::WINDOW_X_MAX && !self.window_full_line) {
+            return;
+        }
+        let tile_map = if control_reg & 0b1000000 == 0 { GPU::BG_MAP_1 } else { GPU::BG_MAP_2 };
+
+        // first pixel of the screen covered by the window, and number of window pixels hidden on the left
+        let (first_x, hidden_pixels) = if self.window_full_line {
+            (0, 0)
+        } else if window_x < 7 {
+            // the window starts off screen ; at WX=0 the fine scroll of the background shifts it as well
+            let stutter = if window_x == 0 { bus.fetch_byte(GPU::SCROLL_X) % 8 } else { 0 };
+            (0, 7 - window_x + stutter)
+        } else {
+            (window_x - 7, 0)
+        };
+
+        for i in first_x..GPU::SCREEN_WIDTH {
+            let window_pixel = i - first_x + hidden_pixels;
+            let color_nb = GPU::fetch_map_pixel(bus, control_reg, tile_map, window_pixel, self.window_line);
+            self.set_background_pixel(bus, i, color_nb);
+        }
+        self.window_line += 1;
+        // at WX=166 the window starts on the last pixel, and spans the whole next line
+        self.window_full_line = window_x == GPU::WINDOW_X_MAX;
+    }
+
+    fn render_sprite_line(&mut self, bus: &bus::Bus) {
+        let control_reg = bus.fetch_byte(GPU::CONTROL_REGISTER);
+        let sprite_height: i32 = if control_reg & 0b100 == 0 { 8 } else { 16 };
+        let line = self.current_line as i32;
+
+        // OAM scan : the first 10 sprites in OAM order covering the line are selected, whatever their X
+        let mut sprites: Vec<(i32, i32, u8, u8)> = Vec::with_capacity(GPU::MAX_SPRITES_PER_LINE);
+        for i in 0..GPU::SPRITES_NB {
             let base_address = GPU::OAM + i * 4;
-            let y_pos = (bus.fetch_byte(base_address) as u16 as i32) - 16;
-            let x_pos = (bus.fetch_byte(base_address + 1) as u16 as i32) - 8;
+            let y_pos = (bus.fetch_byte(base_address) as i32) - 16;
+            if line < y_pos || line >= y_pos + sprite_height {
+                continue;
+            }
+            let x_pos = (bus.fetch_byte(base_address + 1) as i32) - 8;
             let tile_nb = bus.fetch_byte(base_address + 2);
             let flags = bus.fetch_byte(base_address + 3);
-
-            if sprite_size == 0 { // 8x8 sprites
-                if self.check_8x8_sprite_rendering(x_pos, y_pos) == false {
-                    continue; // do not render sprite
-                }
-            } else { // 8x16 sprites
-                // TODO
-                panic!("8x16 sprites unimplemented yet");
-            }
-            // sprite is valid to render
-            let mut sprite: Vec<u8> = (0..16).map(|k| bus.fetch_byte(GPU::TILESET_1 + 16 * (tile_nb as u16) + k)).collect();
-            if flags & 0b1000000 == 1 { // Y flip
-                sprite.reverse();
+            sprites.push((x_pos, y_pos, tile_nb, flags));
+            if sprites.len() == GPU::MAX_SPRITES_PER_LINE {
+                break;
             }
-            // get Y line to render
-            let row_index = ((self.current_line as u16 as i32) - y_pos) as usize;
-            let row_1 = sprite[2 * row_index];
-            let row_2 = sprite[2 * row_index + 1];
+        }
+        // on DMG the sprite with the smallest X is drawn over the others, then the first one in OAM
+        sprites.sort_by_key(|&(x_pos, _, _, _)| x_pos); // the sort is stable and keeps the OAM order
 
-            let x_flip = flags & 0b100000;
+        let mut covered = vec![false; GPU::SCREEN_WIDTH as usize]; // a sprite pixel was already found there
+        for (x_pos, y_pos, tile_nb, flags) in sprites {
+            let behind_background = flags & 0b10000000 != 0;
+            let palette = if flags & 0b10000 == 0 { GPU::OBJ_PALETTE_0 } else { GPU::OBJ_PALETTE_1 };
+            let mut row = (line - y_pos) as u8;
+            if flags & 0b1000000 != 0 { // Y flip
+                row = (sprite_height as u8) - 1 - row;
+            }
+            // in 8x16 mode the top tile is the even one, the bottom tile follows it
+            let tile_nb = if sprite_height == 16 { tile_nb & 0xFE } else { tile_nb };
+            let tile_address = GPU::TILESET_1 + 16 * (tile_nb as u16); // sprites always use the unsigned tile numbers
 
             for i in 0..8 {
-                if x_flip == 1 {
-                    let shade_1 = (row_1 & (0b10000000 >> (7 - i))) >> (i);
-                    let shade_2 = (row_2 & (0b10000000 >> (7 - i))) >> (i);
-                    let shade = shade_1 + (shade_2 << 1);
-                    self.choose_color_from_palette(bus, canvas, shade);
-                    canvas.draw_point(sdl2::rect::Point::new(x_pos + i, self.current_line as i32)).unwrap();
-                } else {
-                    let shade_1 = (row_1 & (0b10000000 >> i)) >> (7 - i);
-                    let shade_2 = (row_2 & (0b10000000 >> i)) >> (7 - i);
-                    let shade = shade_1 + (shade_2 << 1);
-                    self.choose_color_from_palette(bus, canvas, shade);
-                    canvas.draw_point(sdl2::rect::Point::new(x_pos + i, self.current_line as i32)).unwrap();
+                let x = x_pos + i;
+                if x < 0 || x >= GPU::SCREEN_WIDTH as i32 || covered[x as usize] {
+                    continue;
                 }
+                let pos_x_in_sprite = if flags & 0b100000 != 0 { 7 - i as u8 } else { i as u8 }; // X flip
+                let color_nb = GPU::fetch_tile_pixel(bus, tile_address, pos_x_in_sprite, row);
+                if color_nb == 0 {
+                    continue; // transparent, the sprites below may show
+                }
+                // the pixel belongs to this sprite even if hidden by the background
+                covered[x as usize] = true;
+                if behind_background && self.background_line[x as usize] != 0 {
+                    continue;
+                }
+                let color = self.choose_color_from_palette(bus, palette, color_nb);
+                self.set_pixel(x, color);
             }
         }
     }
 
-    fn write_scanline(&self, bus: &bus::Bus, canvas: &mut sdl2::render::Canvas<sdl2::video::Window>) {
+    fn write_scanline(&mut self, bus: &bus::Bus) {
         let control_reg = bus.fetch_byte(GPU::CONTROL_REGISTER);
         let bg_display_flag = control_reg & 1;
         let sprite_display_flag = control_reg & 0b10;
+        let window_display_flag = control_reg & 0b100000;
+        if self.current_line == bus.fetch_byte(GPU::WINDOW_Y) {
+            self.window_triggered = true;
+        }
+        for x in 0..GPU::SCREEN_WIDTH {
+            self.set_pixel(x as i32, GPU::WHITE);
+            self.background_line[x as usize] = 0;
+        }
         if bg_display_flag != 0 {
-            self.render_background_line(bus, canvas);
+            self.render_background_line(bus);
+            // the window is disabled along with the background on DMG
+            if window_display_flag != 0 {
+                self.render_window_line(bus);
+            }
         }
         if sprite_display_flag != 0 {
-            self.render_sprite_line(bus, canvas);
+            self.render_sprite_line(bus);
         }
     }
-
-    fn render_canvas(&self, canvas: &mut sdl2::render::Canvas<sdl2::video::Window>) {
-        canvas.present();
-        canvas.set_draw_color(sdl2::pixels::Color::WHITE);
-        canvas.clear();
-    }
-}
\ No newline at end of file
+}
@@ -0,0 +1,215 @@
+pub struct CartridgeHeader {
+    pub title: String,
+    pub manufacturer_code: String,
+    pub cgb_flag: u8,
+    pub licensee_code: String, // new licensee code if the old one is 0x33, else the old code in hexadecimal
+    pub sgb_flag: u8,
+    pub cartridge_type: u8,
+    pub rom_size_code: u8,
+    pub rom_size: usize, // 0 if the size code is unknown
+    pub ram_size: usize,
+    pub destination: u8,
+    pub version: u8,
+    pub header_checksum: u8,
+    pub computed_header_checksum: u8,
+    pub global_checksum: u16,
+    pub computed_global_checksum: u16,
+    file_size: usize,
+}
+
+impl CartridgeHeader {
+    const TITLE: usize = 0x134;
+    const MANUFACTURER_CODE: usize = 0x13F;
+    const CGB_FLAG: usize = 0x143;
+    const NEW_LICENSEE_CODE: usize = 0x144;
+    const SGB_FLAG: usize = 0x146;
+    const CARTRIDGE_TYPE: usize = 0x147;
+    const ROM_SIZE: usize = 0x148;
+    const RAM_SIZE: usize = 0x149;
+    const DESTINATION: usize = 0x14A;
+    const OLD_LICENSEE_CODE: usize = 0x14B;
+    const VERSION: usize = 0x14C;
+    const HEADER_CHECKSUM: usize = 0x14D;
+    const GLOBAL_CHECKSUM: usize = 0x14E;
+    const HEADER_END: usize = 0x150;
+
+    pub fn parse(rom: &[u8]) -> Result<CartridgeHeader, String> {
+        if rom.len() < CartridgeHeader::HEADER_END {
+            return Err(format!("ROM is too small to contain a cartridge header ({} bytes)", rom.len()));
+        }
+
+        let cgb_flag = rom[CartridgeHeader::CGB_FLAG];
+        // newer cartridges use the end of the title area for the manufacturer code and the CGB flag
+        let (title_end, manufacturer_code) = if cgb_flag & 0x80 != 0 {
+            (CartridgeHeader::MANUFACTURER_CODE, CartridgeHeader::read_string(&rom[CartridgeHeader::MANUFACTURER_CODE..CartridgeHeader::CGB_FLAG]))
+        } else {
+            (CartridgeHeader::CGB_FLAG + 1, String::new())
+        };
+
+        let old_licensee_code = rom[CartridgeHeader::OLD_LICENSEE_CODE];
+        let licensee_code = if old_licensee_code == 0x33 {
+            CartridgeHeader::read_string(&rom[CartridgeHeader::NEW_LICENSEE_CODE..CartridgeHeader::SGB_FLAG])
+        } else {
+            format!("{:02X}", old_licensee_code)
+        };
+
+        let rom_size_code = rom[CartridgeHeader::ROM_SIZE];
+        // banking uses the size of the file, an unknown code is only reported by warnings
+        let rom_size = match rom_size_code {
+            0x00..=0x08 => 0x8000 << rom_size_code,
+            0x52 => 72 * 0x4000,
+            0x53 => 80 * 0x4000,
+            0x54 => 96 * 0x4000,
+            _ => 0,
+        };
+        let ram_size = match rom[CartridgeHeader::RAM_SIZE] {
+            0x01 => 0x800,
+            0x02 => 0x2000,
+            0x03 => 0x8000,
+            0x04 => 0x20000,
+            0x05 => 0x10000,
+            _ => 0,
+        };
+
+        let mut computed_header_checksum: u8 = 0;
+        for byte in &rom[CartridgeHeader::TITLE..CartridgeHeader::HEADER_CHECKSUM] {
+            computed_header_checksum = computed_header_checksum.wrapping_sub(*byte).wrapping_sub(1);
+        }
+        let mut computed_global_checksum: u16 = 0;
+        for (i, byte) in rom.iter().enumerate() {
+            if i != CartridgeHeader::GLOBAL_CHECKSUM && i != CartridgeHeader::GLOBAL_CHECKSUM + 1 {
+                computed_global_checksum = computed_global_checksum.wrapping_add(*byte as u16);
+            }
+        }
+
+        Ok(CartridgeHeader {
+            title: CartridgeHeader::read_string(&rom[CartridgeHeader::TITLE..title_end]),
+            manufacturer_code,
+            cgb_flag,
+            licensee_code,
+            sgb_flag: rom[CartridgeHeader::SGB_FLAG],
+            cartridge_type: rom[CartridgeHeader::CARTRIDGE_TYPE],
+            rom_size_code,
+            rom_size,
+            ram_size,
+            destination: rom[CartridgeHeader::DESTINATION],
+            version: rom[CartridgeHeader::VERSION],
+            header_checksum: rom[CartridgeHeader::HEADER_CHECKSUM],
+            computed_header_checksum,
+            global_checksum: ((rom[CartridgeHeader::GLOBAL_CHECKSUM] as u16) << 8) + (rom[CartridgeHeader::GLOBAL_CHECKSUM + 1] as u16),
+            computed_global_checksum,
+            file_size: rom.len(),
+        })
+    }
+
+    fn read_string(bytes: &[u8]) -> String {
+        // header strings are upper case ASCII padded with zeros
+        bytes.iter()
+            .take_while(|&&c| c != 0)
+            .map(|&c| if c.is_ascii_graphic() || c == b' ' { c as char } else { '?' })
+            .collect::<String>()
+            .trim_end()
+            .to_string()
+    }
+
+    pub fn header_checksum_valid(&self) -> bool {
+        self.header_checksum == self.computed_header_checksum
+    }
+
+    pub fn global_checksum_valid(&self) -> bool {
+        self.global_checksum == self.computed_global_checksum
+    }
+
+    pub fn has_battery(&self) -> bool {
+        matches!(self.cartridge_type, 0x03 | 0x06 | 0x09 | 0x0D | 0x0F | 0x10 | 0x13 | 0x1B | 0x1E | 0x22 | 0xFF)
+    }
+
+    pub fn cartridge_type_name(&self) -> &'static str {
+        match self.cartridge_type {
+            0x00 => "ROM ONLY",
+            0x01 => "MBC1",
+            0x02 => "MBC1+RAM",
+            0x03 => "MBC1+RAM+BATTERY",
+            0x05 => "MBC2",
+            0x06 => "MBC2+BATTERY",
+            0x08 => "ROM+RAM",
+            0x09 => "ROM+RAM+BATTERY",
+            0x0B => "MMM01",
+            0x0C => "MMM01+RAM",
+            0x0D => "MMM01+RAM+BATTERY",
+            0x0F => "MBC3+TIMER+BATTERY",
+            0x10 => "MBC3+TIMER+RAM+BATTERY",
+            0x11 => "MBC3",
+            0x12 => "MBC3+RAM",
+            0x13 => "MBC3+RAM+BATTERY",
+            0x19 => "MBC5",
+            0x1A => "MBC5+RAM",
+            0x1B => "MBC5+RAM+BATTERY",
+            0x1C => "MBC5+RUMBLE",
+            0x1D => "MBC5+RUMBLE+RAM",
+            0x1E => "MBC5+RUMBLE+RAM+BATTERY",
+            0x20 => "MBC6",
+            0x22 => "MBC7+SENSOR+RUMBLE+RAM+BATTERY",
+            0xFC => "POCKET CAMERA",
+            0xFD => "BANDAI TAMA5",
+            0xFE => "HuC3",
+            0xFF => "HuC1+RAM+BATTERY",
+            _ => "UNKNOWN",
+        }
+    }
+
+    pub fn warnings(&self) -> Vec<String> {
+        // inconsistencies between the header and the ROM content
+        let mut warnings = Vec::new();
+        if !self.header_checksum_valid() {
+            warnings.push(format!(
+                "Header checksum mismatch : expected {:#04x}, computed {:#04x}",
+                self.header_checksum, self.computed_header_checksum
+            ));
+        }
+        if !self.global_checksum_valid() {
+            warnings.push(format!(
+                "Global checksum mismatch : expected {:#06x}, computed {:#06x}",
+                self.global_checksum, self.computed_global_checksum
+            ));
+        }
+        if self.rom_size == 0 {
+            warnings.push(format!("Unknown ROM size code {:#04x}", self.rom_size_code));
+        } else if self.file_size != self.rom_size {
+            warnings.push(format!(
+                "ROM size mismatch : header declares {} bytes, file has {} bytes",
+                self.rom_size, self.file_size
+            ));
+        }
+        warnings
+    }
+
+    pub fn describe(&self) -> String {
+        let mut description = String::new();
+        description += &format!("Title: {}\n", self.title);
+        if !self.manufacturer_code.is_empty() {
+            description += &format!("Manufacturer: {}\n", self.manufacturer_code);
+        }
+        description += &format!("Licensee: {}\n", self.licensee_code);
+        description += &format!("CGB flag: {:#04x}   SGB flag: {:#04x}\n", self.cgb_flag, self.sgb_flag);
+        description += &format!("Type: {:#04x} ({})\n", self.cartridge_type, self.cartridge_type_name());
+        if self.rom_size == 0 {
+            description += &format!("ROM size: unknown (code {:#04x})   RAM size: {} KiB\n", self.rom_size_code, self.ram_size / 1024);
+        } else {
+            description += &format!("ROM size: {} KiB   RAM size: {} KiB\n", self.rom_size / 1024, self.ram_size / 1024);
+        }
+        description += &format!("Destination: {}\n", if self.destination == 0 { "Japan" } else { "Overseas" });
+        description += &format!("Version: {}\n", self.version);
+        description += &format!(
+            "Header checksum: {:#04x} ({})\n",
+            self.header_checksum,
+            if self.header_checksum_valid() { "ok" } else { "MISMATCH" }
+        );
+        description += &format!(
+            "Global checksum: {:#06x} ({})",
+            self.global_checksum,
+            if self.global_checksum_valid() { "ok" } else { "MISMATCH" }
+        );
+        description
+    }
+}
@@ -7,1537 +7,1281 @@ impl Instruction<'_> {
         Instruction {
             disassembly: "RLC B",
             op_len: 2,
-            clock_cycles: 2,
             execute: rlc_b,
         },
         Instruction {
             disassembly: "RLC C",
             op_len: 2,
-            clock_cycles: 2,
             execute: rlc_c,
         },
         Instruction {
             disassembly: "RLC D",
             op_len: 2,
-            clock_cycles: 2,
             execute: rlc_d,
         },
         Instruction {
             disassembly: "RLC E",
             op_len: 2,
-            clock_cycles: 2,
             execute: rlc_e,
         },
         Instruction {
             disassembly: "RLC H",
             op_len: 2,
-            clock_cycles: 2,
             execute: rlc_h,
         },
         Instruction {
             disassembly: "RLC L",
             op_len: 2,
-            clock_cycles: 2,
             execute: rlc_l,
         },
         Instruction {
             disassembly: "RLC (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: rlc_hl_ptr,
         },
         Instruction {
             disassembly: "RLC A",
             op_len: 2,
-            clock_cycles: 2,
             execute: rlc_a,
         },
         Instruction {
             disassembly: "RRC B",
             op_len: 2,
-            clock_cycles: 2,
             execute: rrc_b,
         },
         Instruction {
             disassembly: "RRC C",
             op_len: 2,
-            clock_cycles: 2,
             execute: rrc_c,
         },
         Instruction {
             disassembly: "RRC D",
             op_len: 2,
-            clock_cycles: 2,
             execute: rrc_d,
         },
         Instruction {
             disassembly: "RRC E",
             op_len: 2,
-            clock_cycles: 2,
             execute: rrc_e,
         },
         Instruction {
             disassembly: "RRC H",
             op_len: 2,
-            clock_cycles: 2,
             execute: rrc_h,
         },
         Instruction {
             disassembly: "RRC L",
             op_len: 2,
-            clock_cycles: 2,
             execute: rrc_l,
         },
         Instruction {
             disassembly: "RRC (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: rrc_hl_ptr,
         },
         Instruction {
             disassembly: "RRC A",
             op_len: 2,
-            clock_cycles: 2,
             execute: rrc_a,
         },
         Instruction {
             disassembly: "RL B",
             op_len: 2,
-            clock_cycles: 2,
             execute: rl_b,
         },
         Instruction {
             disassembly: "RL C",
             op_len: 2,
-            clock_cycles: 2,
             execute: rl_c,
         },
         Instruction {
             disassembly: "RL D",
             op_len: 2,
-            clock_cycles: 2,
             execute: rl_d,
         },
         Instruction {
             disassembly: "RL E",
             op_len: 2,
-            clock_cycles: 2,
             execute: rl_e,
         },
         Instruction {
             disassembly: "RL H",
             op_len: 2,
-            clock_cycles: 2,
             execute: rl_h,
         },
         Instruction {
             disassembly: "RL L",
             op_len: 2,
-            clock_cycles: 2,
             execute: rl_l,
         },
         Instruction {
             disassembly: "RL (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: rl_hl_ptr,
         },
         Instruction {
             disassembly: "RL A",
             op_len: 2,
-            clock_cycles: 2,
             execute: rl_a,
         },
         Instruction {
             disassembly: "RR B",
             op_len: 2,
-            clock_cycles: 2,
             execute: rr_b,
         },
         Instruction {
             disassembly: "RR C",
             op_len: 2,
-            clock_cycles: 2,
             execute: rr_c,
         },
         Instruction {
             disassembly: "RR D",
             op_len: 2,
-            clock_cycles: 2,
             execute: rr_d,
         },
         Instruction {
             disassembly: "RR E",
             op_len: 2,
-            clock_cycles: 2,
             execute: rr_e,
         },
         Instruction {
             disassembly: "RR H",
             op_len: 2,
-            clock_cycles: 2,
             execute: rr_h,
         },
         Instruction {
             disassembly: "RR L",
             op_len: 2,
-            clock_cycles: 2,
             execute: rr_l,
         },
         Instruction {
             disassembly: "RR (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: rr_hl_ptr,
         },
         Instruction {
             disassembly: "RR A",
             op_len: 2,
-            clock_cycles: 2,
             execute: rr_a,
         },
         Instruction {
             disassembly: "SLA B",
             op_len: 2,
-            clock_cycles: 2,
             execute: sla_b,
         },
         Instruction {
             disassembly: "SLA C",
             op_len: 2,
-            clock_cycles: 2,
             execute: sla_c,
         },
         Instruction {
             disassembly: "SLA D",
             op_len: 2,
-            clock_cycles: 2,
             execute: sla_d,
         },
         Instruction {
             disassembly: "SLA E",
             op_len: 2,
-            clock_cycles: 2,
             execute: sla_e,
         },
         Instruction {
             disassembly: "SLA H",
             op_len: 2,
-            clock_cycles: 2,
             execute: sla_h,
         },
         Instruction {
             disassembly: "SLA L",
             op_len: 2,
-            clock_cycles: 2,
             execute: sla_l,
         },
         Instruction {
             disassembly: "SLA (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: sla_hl_ptr,
         },
         Instruction {
             disassembly: "SLA A",
             op_len: 2,
-            clock_cycles: 2,
             execute: sla_a,
         },
         Instruction {
             disassembly: "SRA B",
             op_len: 2,
-            clock_cycles: 2,
             execute: sra_b,
         },
         Instruction {
             disassembly: "SRA C",
             op_len: 2,
-            clock_cycles: 2,
             execute: sra_c,
         },
         Instruction {
             disassembly: "SRA D",
             op_len: 2,
-            clock_cycles: 2,
             execute: sra_d,
         },
         Instruction {
             disassembly: "SRA E",
             op_len: 2,
-            clock_cycles: 2,
             execute: sra_e,
         },
         Instruction {
             disassembly: "SRA H",
             op_len: 2,
-            clock_cycles: 2,
             execute: sra_h,
         },
         Instruction {
             disassembly: "SRA L",
             op_len: 2,
-            clock_cycles: 2,
             execute: sra_l,
         },
         Instruction {
             disassembly: "SRA (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: sra_hl_ptr,
         },
         Instruction {
             disassembly: "SRA A",
             op_len: 2,
-            clock_cycles: 2,
             execute: sra_a,
         },
         Instruction {
             disassembly: "SWAP B",
             op_len: 2,
-            clock_cycles: 2,
             execute: swap_b,
         },
         Instruction {
             disassembly: "SWAP C",
             op_len: 2,
-            clock_cycles: 2,
             execute: swap_c,
         },
         Instruction {
             disassembly: "SWAP D",
             op_len: 2,
-            clock_cycles: 2,
             execute: swap_d,
         },
         Instruction {
             disassembly: "SWAP E",
             op_len: 2,
-            clock_cycles: 2,
             execute: swap_e,
         },
         Instruction {
             disassembly: "SWAP H",
             op_len: 2,
-            clock_cycles: 2,
             execute: swap_h,
         },
         Instruction {
             disassembly: "SWAP L",
             op_len: 2,
-            clock_cycles: 2,
             execute: swap_l,
         },
         Instruction {
             disassembly: "SWAP (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: swap_hl_ptr,
         },
         Instruction {
             disassembly: "SWAP A",
             op_len: 2,
-            clock_cycles: 2,
             execute: swap_a,
         },
         Instruction {
             disassembly: "SRL B",
             op_len: 2,
-            clock_cycles: 2,
             execute: srl_b,
         },
         Instruction {
             disassembly: "SRL C",
             op_len: 2,
-            clock_cycles: 2,
             execute: srl_c,
         },
         Instruction {
             disassembly: "SRL D",
             op_len: 2,
-            clock_cycles: 2,
             execute: srl_d,
         },
         Instruction {
             disassembly: "SRL E",
             op_len: 2,
-            clock_cycles: 2,
             execute: srl_e,
         },
         Instruction {
             disassembly: "SRL H",
             op_len: 2,
-            clock_cycles: 2,
             execute: srl_h,
         },
         Instruction {
             disassembly: "SRL L",
             op_len: 2,
-            clock_cycles: 2,
             execute: srl_l,
         },
         Instruction {
             disassembly: "SRL (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: srl_hl_ptr,
         },
         Instruction {
             disassembly: "SRL A",
             op_len: 2,
-            clock_cycles: 2,
             execute: srl_a,
         },
         Instruction {
             disassembly: "BIT 0 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_0_b,
         },
         Instruction {
             disassembly: "BIT 0 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_0_c,
         },
         Instruction {
             disassembly: "BIT 0 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_0_d,
         },
         Instruction {
             disassembly: "BIT 0 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_0_e,
         },
         Instruction {
             disassembly: "BIT 0 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_0_h,
         },
         Instruction {
             disassembly: "BIT 0 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_0_l,
         },
         Instruction {
             disassembly: "BIT 0 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_0_hl_ptr,
         },
         Instruction {
             disassembly: "BIT 0 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_0_a,
         },
         Instruction {
             disassembly: "BIT 1 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_1_b,
         },
         Instruction {
             disassembly: "BIT 1 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_1_c,
         },
         Instruction {
             disassembly: "BIT 1 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_1_d,
         },
         Instruction {
             disassembly: "BIT 1 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_1_e,
         },
         Instruction {
             disassembly: "BIT 1 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_1_h,
         },
         Instruction {
             disassembly: "BIT 1 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_1_l,
         },
         Instruction {
             disassembly: "BIT 1 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_1_hl_ptr,
         },
         Instruction {
             disassembly: "BIT 1 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_1_a,
         },
         Instruction {
             disassembly: "BIT 2 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_2_b,
         },
         Instruction {
             disassembly: "BIT 2 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_2_c,
         },
         Instruction {
             disassembly: "BIT 2 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_2_d,
         },
         Instruction {
             disassembly: "BIT 2 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_2_e,
         },
         Instruction {
             disassembly: "BIT 2 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_2_h,
         },
         Instruction {
             disassembly: "BIT 2 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_2_l,
         },
         Instruction {
             disassembly: "BIT 2 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_2_hl_ptr,
         },
         Instruction {
             disassembly: "BIT 2 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_2_a,
         },
         Instruction {
             disassembly: "BIT 3 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_3_b,
         },
         Instruction {
             disassembly: "BIT 3 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_3_c,
         },
         Instruction {
             disassembly: "BIT 3 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_3_d,
         },
         Instruction {
             disassembly: "BIT 3 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_3_e,
         },
         Instruction {
             disassembly: "BIT 3 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_3_h,
         },
         Instruction {
             disassembly: "BIT 3 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_3_l,
         },
         Instruction {
             disassembly: "BIT 3 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_3_hl_ptr,
         },
         Instruction {
             disassembly: "BIT 3 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_3_a,
         },
         Instruction {
             disassembly: "BIT 4 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_4_b,
         },
         Instruction {
             disassembly: "BIT 4 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_4_c,
         },
         Instruction {
             disassembly: "BIT 4 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_4_d,
         },
         Instruction {
             disassembly: "BIT 4 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_4_e,
         },
         Instruction {
             disassembly: "BIT 4 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_4_h,
         },
         Instruction {
             disassembly: "BIT 4 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_4_l,
         },
         Instruction {
             disassembly: "BIT 4 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_4_hl_ptr,
         },
         Instruction {
             disassembly: "BIT 4 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_4_a,
         },
         Instruction {
             disassembly: "BIT 5 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_5_b,
         },
         Instruction {
             disassembly: "BIT 5 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_5_c,
         },
         Instruction {
             disassembly: "BIT 5 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_5_d,
         },
         Instruction {
             disassembly: "BIT 5 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_5_e,
         },
         Instruction {
             disassembly: "BIT 5 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_5_h,
         },
         Instruction {
             disassembly: "BIT 5 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_5_l,
         },
         Instruction {
             disassembly: "BIT 5 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_5_hl_ptr,
         },
         Instruction {
             disassembly: "BIT 5 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_5_a,
         },
         Instruction {
             disassembly: "BIT 6 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_6_b,
         },
         Instruction {
             disassembly: "BIT 6 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_6_c,
         },
         Instruction {
             disassembly: "BIT 6 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_6_d,
         },
         Instruction {
             disassembly: "BIT 6 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_6_e,
         },
         Instruction {
             disassembly: "BIT 6 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_6_h,
         },
         Instruction {
             disassembly: "BIT 6 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_6_l,
         },
         Instruction {
             disassembly: "BIT 6 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_6_hl_ptr,
         },
         Instruction {
             disassembly: "BIT 6 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_6_a,
         },
         Instruction {
             disassembly: "BIT 7 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_7_b,
         },
         Instruction {
             disassembly: "BIT 7 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_7_c,
         },
         Instruction {
             disassembly: "BIT 7 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_7_d,
         },
         Instruction {
             disassembly: "BIT 7 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_7_e,
         },
         Instruction {
             disassembly: "BIT 7 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_7_h,
         },
         Instruction {
             disassembly: "BIT 7 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_7_l,
         },
         Instruction {
             disassembly: "BIT 7 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_7_hl_ptr,
         },
         Instruction {
             disassembly: "BIT 7 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: bit_7_a,
         },
         Instruction {
             disassembly: "RES 0 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_0_b,
         },
         Instruction {
             disassembly: "RES 0 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_0_c,
         },
         Instruction {
             disassembly: "RES 0 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_0_d,
         },
         Instruction {
             disassembly: "RES 0 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_0_e,
         },
         Instruction {
             disassembly: "RES 0 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_0_h,
         },
         Instruction {
             disassembly: "RES 0 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_0_l,
         },
         Instruction {
             disassembly: "RES 0 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_0_hl_ptr,
         },
         Instruction {
             disassembly: "RES 0 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_0_a,
         },
         Instruction {
             disassembly: "RES 1 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_1_b,
         },
         Instruction {
             disassembly: "RES 1 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_1_c,
         },
         Instruction {
             disassembly: "RES 1 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_1_d,
         },
         Instruction {
             disassembly: "RES 1 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_1_e,
         },
         Instruction {
             disassembly: "RES 1 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_1_h,
         },
         Instruction {
             disassembly: "RES 1 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_1_l,
         },
         Instruction {
             disassembly: "RES 1 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_1_hl_ptr,
         },
         Instruction {
             disassembly: "RES 1 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_1_a,
         },
         Instruction {
             disassembly: "RES 2 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_2_b,
         },
         Instruction {
             disassembly: "RES 2 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_2_c,
         },
         Instruction {
             disassembly: "RES 2 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_2_d,
         },
         Instruction {
             disassembly: "RES 2 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_2_e,
         },
         Instruction {
             disassembly: "RES 2 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_2_h,
         },
         Instruction {
             disassembly: "RES 2 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_2_l,
         },
         Instruction {
             disassembly: "RES 2 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_2_hl_ptr,
         },
         Instruction {
             disassembly: "RES 2 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_2_a,
         },
         Instruction {
             disassembly: "RES 3 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_3_b,
         },
         Instruction {
             disassembly: "RES 3 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_3_c,
         },
         Instruction {
             disassembly: "RES 3 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_3_d,
         },
         Instruction {
             disassembly: "RES 3 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_3_e,
         },
         Instruction {
             disassembly: "RES 3 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_3_h,
         },
         Instruction {
             disassembly: "RES 3 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_3_l,
         },
         Instruction {
             disassembly: "RES 3 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_3_hl_ptr,
         },
         Instruction {
             disassembly: "RES 3 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_3_a,
         },
         Instruction {
             disassembly: "RES 4 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_4_b,
         },
         Instruction {
             disassembly: "RES 4 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_4_c,
         },
         Instruction {
             disassembly: "RES 4 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_4_d,
         },
         Instruction {
             disassembly: "RES 4 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_4_e,
         },
         Instruction {
             disassembly: "RES 4 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_4_h,
         },
         Instruction {
             disassembly: "RES 4 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_4_l,
         },
         Instruction {
             disassembly: "RES 4 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_4_hl_ptr,
         },
         Instruction {
             disassembly: "RES 4 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_4_a,
         },
         Instruction {
             disassembly: "RES 5 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_5_b,
         },
         Instruction {
             disassembly: "RES 5 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_5_c,
         },
         Instruction {
             disassembly: "RES 5 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_5_d,
         },
         Instruction {
             disassembly: "RES 5 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_5_e,
         },
         Instruction {
             disassembly: "RES 5 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_5_h,
         },
         Instruction {
             disassembly: "RES 5 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_5_l,
         },
         Instruction {
             disassembly: "RES 5 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_5_hl_ptr,
         },
         Instruction {
             disassembly: "RES 5 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_5_a,
         },
         Instruction {
             disassembly: "RES 6 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_6_b,
         },
         Instruction {
             disassembly: "RES 6 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_6_c,
         },
         Instruction {
             disassembly: "RES 6 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_6_d,
         },
         Instruction {
             disassembly: "RES 6 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_6_e,
         },
         Instruction {
             disassembly: "RES 6 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_6_h,
         },
         Instruction {
             disassembly: "RES 6 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_6_l,
         },
         Instruction {
             disassembly: "RES 6 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_6_hl_ptr,
         },
         Instruction {
             disassembly: "RES 6 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_6_a,
         },
         Instruction {
             disassembly: "RES 7 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_7_b,
         },
         Instruction {
             disassembly: "RES 7 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_7_c,
         },
         Instruction {
             disassembly: "RES 7 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_7_d,
         },
         Instruction {
             disassembly: "RES 7 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_7_e,
         },
         Instruction {
             disassembly: "RES 7 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_7_h,
         },
         Instruction {
             disassembly: "RES 7 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_7_l,
         },
         Instruction {
             disassembly: "RES 7 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_7_hl_ptr,
         },
         Instruction {
             disassembly: "RES 7 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: res_7_a,
         },
         Instruction {
             disassembly: "SET 0 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_0_b,
         },
         Instruction {
             disassembly: "SET 0 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_0_c,
         },
         Instruction {
             disassembly: "SET 0 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_0_d,
         },
         Instruction {
             disassembly: "SET 0 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_0_e,
         },
         Instruction {
             disassembly: "SET 0 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_0_h,
         },
         Instruction {
             disassembly: "SET 0 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_0_l,
         },
         Instruction {
             disassembly: "SET 0 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_0_hl_ptr,
         },
         Instruction {
             disassembly: "SET 0 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_0_a,
         },
         Instruction {
             disassembly: "SET 1 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_1_b,
         },
         Instruction {
             disassembly: "SET 1 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_1_c,
         },
         Instruction {
             disassembly: "SET 1 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_1_d,
         },
         Instruction {
             disassembly: "SET 1 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_1_e,
         },
         Instruction {
             disassembly: "SET 1 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_1_h,
         },
         Instruction {
             disassembly: "SET 1 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_1_l,
         },
         Instruction {
             disassembly: "SET 1 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_1_hl_ptr,
         },
         Instruction {
             disassembly: "SET 1 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_1_a,
         },
         Instruction {
             disassembly: "SET 2 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_2_b,
         },
         Instruction {
             disassembly: "SET 2 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_2_c,
         },
         Instruction {
             disassembly: "SET 2 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_2_d,
         },
         Instruction {
             disassembly: "SET 2 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_2_e,
         },
         Instruction {
             disassembly: "SET 2 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_2_h,
         },
         Instruction {
             disassembly: "SET 2 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_2_l,
         },
         Instruction {
             disassembly: "SET 2 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_2_hl_ptr,
         },
         Instruction {
             disassembly: "SET 2 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_2_a,
         },
         Instruction {
             disassembly: "SET 3 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_3_b,
         },
         Instruction {
             disassembly: "SET 3 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_3_c,
         },
         Instruction {
             disassembly: "SET 3 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_3_d,
         },
         Instruction {
             disassembly: "SET 3 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_3_e,
         },
         Instruction {
             disassembly: "SET 3 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_3_h,
         },
         Instruction {
             disassembly: "SET 3 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_3_l,
         },
         Instruction {
             disassembly: "SET 3 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_3_hl_ptr,
         },
         Instruction {
             disassembly: "SET 3 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_3_a,
         },
         Instruction {
             disassembly: "SET 4 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_4_b,
         },
         Instruction {
             disassembly: "SET 4 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_4_c,
         },
         Instruction {
             disassembly: "SET 4 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_4_d,
         },
         Instruction {
             disassembly: "SET 4 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_4_e,
         },
         Instruction {
             disassembly: "SET 4 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_4_h,
         },
         Instruction {
             disassembly: "SET 4 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_4_l,
         },
         Instruction {
             disassembly: "SET 4 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_4_hl_ptr,
         },
         Instruction {
             disassembly: "SET 4 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_4_a,
         },
         Instruction {
             disassembly: "SET 5 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_5_b,
         },
         Instruction {
             disassembly: "SET 5 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_5_c,
         },
         Instruction {
             disassembly: "SET 5 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_5_d,
         },
         Instruction {
             disassembly: "SET 5 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_5_e,
         },
         Instruction {
             disassembly: "SET 5 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_5_h,
         },
         Instruction {
             disassembly: "SET 5 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_5_l,
         },
         Instruction {
             disassembly: "SET 5 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_5_hl_ptr,
         },
         Instruction {
             disassembly: "SET 5 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_5_a,
         },
         Instruction {
             disassembly: "SET 6 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_6_b,
         },
         Instruction {
             disassembly: "SET 6 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_6_c,
         },
         Instruction {
             disassembly: "SET 6 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_6_d,
         },
         Instruction {
             disassembly: "SET 6 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_6_e,
         },
         Instruction {
             disassembly: "SET 6 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_6_h,
         },
         Instruction {
             disassembly: "SET 6 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_6_l,
         },
         Instruction {
             disassembly: "SET 6 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_6_hl_ptr,
         },
         Instruction {
             disassembly: "SET 6 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_6_a,
         },
         Instruction {
             disassembly: "SET 7 B",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_7_b,
         },
         Instruction {
             disassembly: "SET 7 C",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_7_c,
         },
         Instruction {
             disassembly: "SET 7 D",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_7_d,
         },
         Instruction {
             disassembly: "SET 7 E",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_7_e,
         },
         Instruction {
             disassembly: "SET 7 H",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_7_h,
         },
         Instruction {
             disassembly: "SET 7 L",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_7_l,
         },
         Instruction {
             disassembly: "SET 7 (HL)",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_7_hl_ptr,
         },
         Instruction {
             disassembly: "SET 7 A",
             op_len: 2,
-            clock_cycles: 2,
             execute: set_7_a,
         },
     ];
@@ -1547,1910 +1291,1448 @@ impl Instruction<'_> {
 // ======================================================
 // 0x0X Instructions
 // ======================================================
-fn rlc_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    let final_bit = cpu.bc.high & 0b10000000;
-    cpu.bc.high = cpu.bc.high << 1;
-    if final_bit == 1 {
-        cpu.set_flag('c');
-        cpu.bc.high |= 1;
-    } else {
-        cpu.clear_flag('c');
-    }
-    cpu.update_flag('z', cpu.bc.high == 0);
-}
-
-fn rlc_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    let final_bit = cpu.bc.low & 0b10000000;
-    cpu.bc.low = cpu.bc.low << 1;
-    if final_bit == 1 {
-        cpu.set_flag('c');
-        cpu.bc.low |= 1;
-    } else {
-        cpu.clear_flag('c');
-    }
-    cpu.update_flag('z', cpu.bc.low == 0);
-}
-
-fn rlc_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    let final_bit = cpu.de.high & 0b10000000;
-    cpu.de.high = cpu.de.high << 1;
-    if final_bit == 1 {
-        cpu.set_flag('c');
-        cpu.de.high |= 1;
-    } else {
-        cpu.clear_flag('c');
-    }
-    cpu.update_flag('z', cpu.de.high == 0);
-}
-
-fn rlc_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    let final_bit = cpu.de.low & 0b10000000;
-    cpu.de.low = cpu.de.low << 1;
-    if final_bit == 1 {
-        cpu.set_flag('c');
-        cpu.de.low |= 1;
-    } else {
-        cpu.clear_flag('c');
-    }
-    cpu.update_flag('z', cpu.de.low == 0);
-}
-
-fn rlc_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    let final_bit = cpu.hl.high & 0b10000000;
-    cpu.hl.high = cpu.hl.high << 1;
-    if final_bit == 1 {
-        cpu.set_flag('c');
-        cpu.hl.high |= 1;
-    } else {
-        cpu.clear_flag('c');
-    }
-    cpu.update_flag('z', cpu.hl.high == 0);
-}
-
-fn rlc_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    let final_bit = cpu.hl.low & 0b10000000;
-    cpu.hl.low = cpu.hl.low << 1;
-    if final_bit == 1 {
-        cpu.set_flag('c');
-        cpu.hl.low |= 1;
-    } else {
-        cpu.clear_flag('c');
-    }
-    cpu.update_flag('z', cpu.hl.low == 0);
-}
-
-fn rlc_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    let final_bit = op;
-    let mut new_op = op << 1;
-    if final_bit == 1 {
-        cpu.set_flag('c');
-        new_op |= 1;
-    } else {
-        cpu.clear_flag('c');
-    }
-    cpu.update_flag('z', new_op == 0);
-    bus.set_byte(cpu.hl.get_combined(), new_op);
-}
-
-fn rlc_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    let final_bit = cpu.af.high & 0b10000000;
-    cpu.af.high = cpu.af.high << 1;
-    if final_bit == 1 {
-        cpu.set_flag('c');
-        cpu.af.high |= 1;
-    } else {
-        cpu.clear_flag('c');
-    }
-    cpu.update_flag('z', cpu.af.high == 0);
-}
-
-fn rrc_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    let first_bit = cpu.bc.high & 1;
-    cpu.bc.high = cpu.bc.high >> 1;
-    if first_bit == 1 {
-        cpu.set_flag('c');
-        cpu.bc.high |= 0b10000000;
-    } else {
-        cpu.clear_flag('c');
-    }
-    cpu.update_flag('z', cpu.bc.high == 0);
-}
-
-fn rrc_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    let first_bit = cpu.bc.low & 1;
-    cpu.bc.low = cpu.bc.low >> 1;
-    if first_bit == 1 {
-        cpu.set_flag('c');
-        cpu.bc.low |= 0b10000000;
-    } else {
-        cpu.clear_flag('c');
-    }
-    cpu.update_flag('z', cpu.bc.low == 0);
-}
-
-fn rrc_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    let first_bit = cpu.de.high & 1;
-    cpu.de.high = cpu.de.high >> 1;
-    if first_bit == 1 {
-        cpu.set_flag('c');
-        cpu.de.high |= 0b10000000;
-    } else {
-        cpu.clear_flag('c');
-    }
-    cpu.update_flag('z', cpu.bc.high == 0);
-}
-
-fn rrc_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    let first_bit = cpu.de.low & 1;
-    cpu.de.low = cpu.de.low >> 1;
-    if first_bit == 1 {
-        cpu.set_flag('c');
-        cpu.de.low |= 0b10000000;
-    } else {
-        cpu.clear_flag('c');
-    }
-    cpu.update_flag('z', cpu.de.low == 0);
-}
-
-fn rrc_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    let first_bit = cpu.hl.high & 1;
-    cpu.hl.high = cpu.hl.high >> 1;
-    if first_bit == 1 {
-        cpu.set_flag('c');
-        cpu.hl.high |= 0b10000000;
-    } else {
-        cpu.clear_flag('c');
-    }
-    cpu.update_flag('z', cpu.hl.high == 0);
-}
-
-fn rrc_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    let first_bit = cpu.hl.low & 1;
-    cpu.hl.low = cpu.hl.low >> 1;
-    if first_bit == 1 {
-        cpu.set_flag('c');
-        cpu.hl.low |= 0b10000000;
-    } else {
-        cpu.clear_flag('c');
-    }
-    cpu.update_flag('z', cpu.hl.low == 0);
-}
-
-fn rrc_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    let first_bit = op & 1;
-    let mut new_op = op >> 1;
-    if first_bit == 1 {
-        cpu.set_flag('c');
-        new_op |= 0b10000000;
-    } else {
-        cpu.clear_flag('c');
-    }
-    cpu.update_flag('z', new_op == 0);
-    bus.set_byte(cpu.hl.get_combined(), new_op);
+fn rlc_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.bc.high = rlc(cpu, cpu.bc.high);
+    2
 }
 
-
-fn rrc_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    let first_bit = cpu.af.high & 1;
-    cpu.af.high = cpu.af.high >> 1;
-    if first_bit == 1 {
-        cpu.set_flag('c');
-        cpu.af.high |= 0b10000000;
-    } else {
-        cpu.clear_flag('c');
-    }
-    cpu.update_flag('z', cpu.af.high == 0);
+fn rlc_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.bc.low = rlc(cpu, cpu.bc.low);
+    2
 }
 
-// ======================================================
-// 0x1X Instructions
-// ======================================================
-fn rl_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let highest_bit = cpu.bc.high & 0b1000000;
-    cpu.bc.high = cpu.bc.high << 1;
-    if cpu.extract_flag('c') == true {
-        // if carry flag is set
-        cpu.bc.high |= 1; // set bit 0 to 1
-    } else {
-        cpu.bc.high &= 0b11111110; // else discard first bit just in case
-    }
-    cpu.update_flag('c', highest_bit == 1);
-    cpu.clear_flag('h');
-    cpu.update_flag('z', cpu.bc.high == 0);
-    cpu.clear_flag('n');
+fn rlc_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.de.high = rlc(cpu, cpu.de.high);
+    2
 }
 
-fn rl_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let highest_bit = cpu.bc.low & 0b1000000;
-    cpu.bc.low = cpu.bc.low << 1;
-    if cpu.extract_flag('c') == true {
-        // if carry flag is set
-        cpu.bc.low |= 1; // set bit 0 to 1
-    } else {
-        cpu.bc.low &= 0b11111110; // else discard first bit just in case
-    }
-    cpu.update_flag('c', highest_bit == 1);
-    cpu.clear_flag('h');
-    cpu.update_flag('z', cpu.bc.low == 0);
-    cpu.clear_flag('n');
-}
-
-fn rl_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let highest_bit = cpu.de.high & 0b1000000;
-    cpu.de.high = cpu.de.high << 1;
-    if cpu.extract_flag('c') == true {
-        // if carry flag is set
-        cpu.de.high |= 1; // set bit 0 to 1
-    } else {
-        cpu.de.high &= 0b11111110; // else discard first bit just in case
-    }
-    cpu.update_flag('c', highest_bit == 1);
-    cpu.clear_flag('h');
-    cpu.update_flag('z', cpu.de.high == 0);
-    cpu.clear_flag('n');
+fn rlc_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.de.low = rlc(cpu, cpu.de.low);
+    2
 }
 
-fn rl_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let highest_bit = cpu.de.low & 0b1000000;
-    cpu.de.low = cpu.de.low << 1;
-    if cpu.extract_flag('c') == true {
-        // if carry flag is set
-        cpu.de.low |= 1; // set bit 0 to 1
-    } else {
-        cpu.de.low &= 0b11111110; // else discard first bit just in case
-    }
-    cpu.update_flag('c', highest_bit == 1);
-    cpu.clear_flag('h');
-    cpu.update_flag('z', cpu.de.low == 0);
-    cpu.clear_flag('n');
-}
-
-fn rl_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let highest_bit = cpu.hl.high & 0b1000000;
-    cpu.hl.high = cpu.hl.high << 1;
-    if cpu.extract_flag('c') == true {
-        // if carry flag is set
-        cpu.hl.high |= 1; // set bit 0 to 1
-    } else {
-        cpu.hl.high &= 0b11111110; // else discard first bit just in case
-    }
-    cpu.update_flag('c', highest_bit == 1);
-    cpu.clear_flag('h');
-    cpu.update_flag('z', cpu.hl.high == 0);
-    cpu.clear_flag('n');
+fn rlc_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.hl.high = rlc(cpu, cpu.hl.high);
+    2
 }
 
-fn rl_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let highest_bit = cpu.hl.low & 0b1000000;
-    cpu.hl.low = cpu.hl.low << 1;
-    if cpu.extract_flag('c') == true {
-        // if carry flag is set
-        cpu.hl.low |= 1; // set bit 0 to 1
-    } else {
-        cpu.hl.low &= 0b11111110; // else discard first bit just in case
-    }
-    cpu.update_flag('c', highest_bit == 1);
-    cpu.clear_flag('h');
-    cpu.update_flag('z', cpu.hl.low == 0);
-    cpu.clear_flag('n');
+fn rlc_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.hl.low = rlc(cpu, cpu.hl.low);
+    2
 }
 
-fn rl_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    let highest_bit = op & 0b1000000;
-    let mut new_op = op << 1;
-    if cpu.extract_flag('c') == true {
-        // if carry flag is set
-        new_op |= 1;
-    } else {
-        new_op &= 0b11111110;
-    }
-    bus.set_byte(cpu.hl.get_combined(), new_op);
-    cpu.update_flag('c', highest_bit == 1);
-    cpu.clear_flag('h');
-    cpu.update_flag('z', new_op == 0);
-    cpu.clear_flag('n');
+fn rlc_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let address = cpu.hl.get_combined();
+    let op = cpu.read_byte(bus, address);
+    let result = rlc(cpu, op);
+    cpu.write_byte(bus, address, result);
+    4
 }
 
-fn rl_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let highest_bit = cpu.af.high & 0b1000000;
-    cpu.af.high = cpu.af.high << 1;
-    if cpu.extract_flag('c') == true {
-        // if carry flag is set
-        cpu.af.high |= 1; // set bit 0 to 1
-    } else {
-        cpu.af.high &= 0b11111110; // else discard first bit just in case
-    }
-    cpu.update_flag('c', highest_bit == 1);
-    cpu.clear_flag('h');
-    cpu.update_flag('z', cpu.af.high == 0);
-    cpu.clear_flag('n');
+fn rlc_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.af.high = rlc(cpu, cpu.af.high);
+    2
 }
 
-fn rr_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lowest_bit = cpu.bc.high & 1;
-    cpu.bc.high = cpu.bc.high >> 1;
-    if cpu.extract_flag('c') == true {
-        cpu.bc.high |= 0b10000000;
-    } else {
-        cpu.bc.high &= 0b01111111;
-    }
-    cpu.update_flag('c', lowest_bit == 1);
-    cpu.update_flag('z', cpu.bc.high == 0);
-    cpu.clear_flag('n');
-    cpu.clear_flag('h');
+fn rrc_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.bc.high = rrc(cpu, cpu.bc.high);
+    2
 }
 
-fn rr_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lowest_bit = cpu.bc.low & 1;
-    cpu.bc.low = cpu.bc.low >> 1;
-    if cpu.extract_flag('c') == true {
-        cpu.bc.low |= 0b10000000;
-    } else {
-        cpu.bc.low &= 0b01111111;
-    }
-    cpu.update_flag('c', lowest_bit == 1);
-    cpu.update_flag('z', cpu.bc.low == 0);
-    cpu.clear_flag('n');
-    cpu.clear_flag('h');
+fn rrc_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.bc.low = rrc(cpu, cpu.bc.low);
+    2
 }
 
-fn rr_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lowest_bit = cpu.de.high & 1;
-    cpu.de.high = cpu.de.high >> 1;
-    if cpu.extract_flag('c') == true {
-        cpu.de.high |= 0b10000000;
-    } else {
-        cpu.de.high &= 0b01111111;
-    }
-    cpu.update_flag('c', lowest_bit == 1);
-    cpu.update_flag('z', cpu.de.high == 0);
-    cpu.clear_flag('n');
-    cpu.clear_flag('h');
+fn rrc_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.de.high = rrc(cpu, cpu.de.high);
+    2
 }
 
-fn rr_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lowest_bit = cpu.de.low & 1;
-    cpu.de.low = cpu.de.low >> 1;
-    if cpu.extract_flag('c') == true {
-        cpu.de.low |= 0b10000000;
-    } else {
-        cpu.de.low &= 0b01111111;
-    }
-    cpu.update_flag('c', lowest_bit == 1);
-    cpu.update_flag('z', cpu.de.low == 0);
-    cpu.clear_flag('n');
-    cpu.clear_flag('h');
+fn rrc_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.de.low = rrc(cpu, cpu.de.low);
+    2
 }
 
-fn rr_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lowest_bit = cpu.hl.high & 1;
-    cpu.hl.high = cpu.hl.high >> 1;
-    if cpu.extract_flag('c') == true {
-        cpu.hl.high |= 0b10000000;
-    } else {
-        cpu.hl.high &= 0b01111111;
-    }
-    cpu.update_flag('c', lowest_bit == 1);
-    cpu.update_flag('z', cpu.hl.high == 0);
-    cpu.clear_flag('n');
-    cpu.clear_flag('h');
+fn rrc_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.hl.high = rrc(cpu, cpu.hl.high);
+    2
 }
 
-fn rr_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lowest_bit = cpu.hl.low & 1;
-    cpu.hl.low = cpu.hl.low >> 1;
-    if cpu.extract_flag('c') == true {
-        cpu.hl.low |= 0b10000000;
-    } else {
-        cpu.hl.low &= 0b01111111;
-    }
-    cpu.update_flag('c', lowest_bit == 1);
-    cpu.update_flag('z', cpu.hl.low == 0);
-    cpu.clear_flag('n');
-    cpu.clear_flag('h');
+fn rrc_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.hl.low = rrc(cpu, cpu.hl.low);
+    2
 }
 
-fn rr_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    let lowest_bit = op & 1;
-    let mut new_op = op >> 1;
-    if cpu.extract_flag('c') == true {
-        new_op |= 0b10000000;
-    } else {
-        new_op &= 0b01111111;
-    }
-    bus.set_byte(cpu.hl.get_combined(), new_op);
-    cpu.update_flag('c', lowest_bit == 1);
-    cpu.update_flag('z', new_op == 0);
-    cpu.clear_flag('n');
-    cpu.clear_flag('h');
+fn rrc_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let address = cpu.hl.get_combined();
+    let op = cpu.read_byte(bus, address);
+    let result = rrc(cpu, op);
+    cpu.write_byte(bus, address, result);
+    4
 }
 
-fn rr_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lowest_bit = cpu.af.high & 1;
-    cpu.af.high = cpu.af.high >> 1;
-    if cpu.extract_flag('c') == true {
-        cpu.af.high |= 0b10000000;
-    } else {
-        cpu.af.high &= 0b01111111;
-    }
-    cpu.update_flag('c', lowest_bit == 1);
-    cpu.update_flag('z', cpu.af.high == 0);
-    cpu.clear_flag('n');
-    cpu.clear_flag('h');
+fn rrc_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.af.high = rrc(cpu, cpu.af.high);
+    2
 }
 
 // ======================================================
-// 0x2X Instructions
+// 0x1X Instructions
 // ======================================================
-fn sla_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let highest_bit = cpu.bc.high & 0b10000000;
-    cpu.update_flag('c', highest_bit != 0);
-    cpu.bc.high = cpu.bc.high << 1;
-    cpu.bc.high &= 0b11111110;
+fn rl_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.bc.high = rl(cpu, cpu.bc.high);
+    2
+}
 
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', cpu.bc.high == 0);
+fn rl_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.bc.low = rl(cpu, cpu.bc.low);
+    2
 }
 
-fn sla_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let highest_bit = cpu.bc.low & 0b10000000;
-    cpu.update_flag('c', highest_bit != 0);
-    cpu.bc.low = cpu.bc.low << 1;
-    cpu.bc.low &= 0b11111110;
+fn rl_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.de.high = rl(cpu, cpu.de.high);
+    2
+}
 
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', cpu.bc.low == 0);
+fn rl_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.de.low = rl(cpu, cpu.de.low);
+    2
 }
 
-fn sla_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let highest_bit = cpu.de.high & 0b10000000;
-    cpu.update_flag('c', highest_bit != 0);
-    cpu.de.high = cpu.de.high << 1;
-    cpu.de.high &= 0b11111110;
+fn rl_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.hl.high = rl(cpu, cpu.hl.high);
+    2
+}
 
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', cpu.de.high == 0);
+fn rl_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.hl.low = rl(cpu, cpu.hl.low);
+    2
 }
 
-fn sla_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let highest_bit = cpu.de.low & 0b10000000;
-    cpu.update_flag('c', highest_bit != 0);
-    cpu.de.low = cpu.de.low << 1;
-    cpu.de.low &= 0b11111110;
+fn rl_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let address = cpu.hl.get_combined();
+    let op = cpu.read_byte(bus, address);
+    let result = rl(cpu, op);
+    cpu.write_byte(bus, address, result);
+    4
+}
 
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', cpu.de.low == 0);
+fn rl_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.af.high = rl(cpu, cpu.af.high);
+    2
 }
 
-fn sla_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let highest_bit = cpu.hl.high & 0b10000000;
-    cpu.update_flag('c', highest_bit != 0);
-    cpu.hl.high = cpu.hl.high << 1;
-    cpu.hl.high &= 0b11111110;
+fn rr_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.bc.high = rr(cpu, cpu.bc.high);
+    2
+}
 
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', cpu.hl.high == 0);
+fn rr_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.bc.low = rr(cpu, cpu.bc.low);
+    2
 }
 
-fn sla_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let highest_bit = cpu.hl.low & 0b10000000;
-    cpu.update_flag('c', highest_bit != 0);
-    cpu.hl.low = cpu.hl.low << 1;
-    cpu.hl.low &= 0b11111110;
+fn rr_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.de.high = rr(cpu, cpu.de.high);
+    2
+}
 
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', cpu.hl.low == 0);
+fn rr_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.de.low = rr(cpu, cpu.de.low);
+    2
 }
 
-fn sla_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    let highest_bit = op & 0b10000000;
-    cpu.update_flag('c', highest_bit != 0);
+fn rr_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.hl.high = rr(cpu, cpu.hl.high);
+    2
+}
 
-    let new_op = (op << 1) & 0b11111110;
-    bus.set_byte(cpu.hl.get_combined(), new_op);
+fn rr_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.hl.low = rr(cpu, cpu.hl.low);
+    2
+}
 
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', new_op == 0);
+fn rr_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let address = cpu.hl.get_combined();
+    let op = cpu.read_byte(bus, address);
+    let result = rr(cpu, op);
+    cpu.write_byte(bus, address, result);
+    4
 }
 
-fn sla_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let highest_bit = cpu.af.high & 0b10000000;
-    cpu.update_flag('c', highest_bit != 0);
-    cpu.af.high = cpu.af.high << 1;
-    cpu.af.high &= 0b11111110;
+fn rr_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.af.high = rr(cpu, cpu.af.high);
+    2
+}
 
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', cpu.af.high == 0);
+// ======================================================
+// 0x2X Instructions
+// ======================================================
+fn sla_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.bc.high = sla(cpu, cpu.bc.high);
+    2
 }
 
-fn sra_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lowest_bit = cpu.bc.high & 1;
-    let highest_bit = cpu.bc.high & 0b10000000;
-    cpu.update_flag('c', lowest_bit != 0);
-    cpu.bc.high = ((cpu.bc.high >> 1) & 0b01111111) | highest_bit;
+fn sla_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.bc.low = sla(cpu, cpu.bc.low);
+    2
+}
 
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', cpu.bc.high == 0);
+fn sla_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.de.high = sla(cpu, cpu.de.high);
+    2
 }
 
-fn sra_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lowest_bit = cpu.bc.low & 1;
-    let highest_bit = cpu.bc.high & 0b10000000;
-    cpu.update_flag('c', lowest_bit != 0);
-    cpu.bc.low = ((cpu.bc.low >> 1) & 0b01111111) | highest_bit;
+fn sla_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.de.low = sla(cpu, cpu.de.low);
+    2
+}
 
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', cpu.bc.low == 0);
+fn sla_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.hl.high = sla(cpu, cpu.hl.high);
+    2
 }
 
-fn sra_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lowest_bit = cpu.de.high & 1;
-    let highest_bit = cpu.bc.high & 0b10000000;
-    cpu.update_flag('c', lowest_bit != 0);
-    cpu.de.high = ((cpu.de.high >> 1) & 0b01111111) | highest_bit;
+fn sla_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.hl.low = sla(cpu, cpu.hl.low);
+    2
+}
 
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', cpu.de.high == 0);
+fn sla_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let address = cpu.hl.get_combined();
+    let op = cpu.read_byte(bus, address);
+    let result = sla(cpu, op);
+    cpu.write_byte(bus, address, result);
+    4
 }
 
-fn sra_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lowest_bit = cpu.de.low & 1;
-    let highest_bit = cpu.bc.high & 0b10000000;
-    cpu.update_flag('c', lowest_bit != 0);
-    cpu.de.low = ((cpu.de.low >> 1) & 0b01111111) | highest_bit;
+fn sla_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.af.high = sla(cpu, cpu.af.high);
+    2
+}
 
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', cpu.de.low == 0);
+fn sra_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.bc.high = sra(cpu, cpu.bc.high);
+    2
 }
 
-fn sra_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lowest_bit = cpu.hl.high & 1;
-    let highest_bit = cpu.bc.high & 0b10000000;
-    cpu.update_flag('c', lowest_bit != 0);
-    cpu.hl.high = ((cpu.hl.high >> 1) & 0b01111111) | highest_bit;
+fn sra_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.bc.low = sra(cpu, cpu.bc.low);
+    2
+}
 
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', cpu.hl.high == 0);
+fn sra_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.de.high = sra(cpu, cpu.de.high);
+    2
 }
 
-fn sra_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lowest_bit = cpu.hl.low & 1;
-    let highest_bit = cpu.bc.high & 0b10000000;
-    cpu.update_flag('c', lowest_bit != 0);
-    cpu.hl.low = ((cpu.hl.low >> 1) & 0b01111111) | highest_bit;
+fn sra_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.de.low = sra(cpu, cpu.de.low);
+    2
+}
 
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', cpu.hl.low == 0);
+fn sra_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.hl.high = sra(cpu, cpu.hl.high);
+    2
 }
 
-fn sra_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    let lowest_bit = op & 1;
-    let highest_bit = op & 0b10000000;
-    cpu.update_flag('c', lowest_bit != 0);
-    let new_op = ((op >> 1) & 0b01111111) | highest_bit;
-    bus.set_byte(cpu.hl.get_combined(), new_op);
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', new_op == 0);
+fn sra_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.hl.low = sra(cpu, cpu.hl.low);
+    2
 }
 
-fn sra_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lowest_bit = cpu.af.high & 1;
-    let highest_bit = cpu.bc.high & 0b10000000;
-    cpu.update_flag('c', lowest_bit != 0);
-    cpu.af.high = ((cpu.af.high >> 1) & 0b01111111) | highest_bit;
+fn sra_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let address = cpu.hl.get_combined();
+    let op = cpu.read_byte(bus, address);
+    let result = sra(cpu, op);
+    cpu.write_byte(bus, address, result);
+    4
+}
 
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', cpu.af.high == 0);
+fn sra_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.af.high = sra(cpu, cpu.af.high);
+    2
 }
 
 // ======================================================
 // 0x3X Instructions
 // ======================================================
-fn swap_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lower_bits = cpu.bc.high & 0b1111;
-    let higher_bits = cpu.bc.high & 0b11110000;
-    cpu.bc.high = (higher_bits >> 4) + (lower_bits << 4);
-
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.clear_flag('c');
-    cpu.update_flag('z', cpu.bc.high == 0);
+fn swap_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.bc.high = swap(cpu, cpu.bc.high);
+    2
 }
 
-fn swap_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lower_bits = cpu.bc.low & 0b1111;
-    let higher_bits = cpu.bc.low & 0b11110000;
-    cpu.bc.low = (higher_bits >> 4) + (lower_bits << 4);
-
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.clear_flag('c');
-    cpu.update_flag('z', cpu.bc.low == 0);
+fn swap_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.bc.low = swap(cpu, cpu.bc.low);
+    2
 }
 
-fn swap_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lower_bits = cpu.de.high & 0b1111;
-    let higher_bits = cpu.de.high & 0b11110000;
-    cpu.de.high = (higher_bits >> 4) + (lower_bits << 4);
-
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.clear_flag('c');
-    cpu.update_flag('z', cpu.de.high == 0);
+fn swap_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.de.high = swap(cpu, cpu.de.high);
+    2
 }
 
-fn swap_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lower_bits = cpu.de.low & 0b1111;
-    let higher_bits = cpu.de.low & 0b11110000;
-    cpu.de.low = (higher_bits >> 4) + (lower_bits << 4);
-
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.clear_flag('c');
-    cpu.update_flag('z', cpu.de.low == 0);
+fn swap_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.de.low = swap(cpu, cpu.de.low);
+    2
 }
 
-fn swap_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lower_bits = cpu.hl.high & 0b1111;
-    let higher_bits = cpu.hl.high & 0b11110000;
-    cpu.hl.high = (higher_bits >> 4) + (lower_bits << 4);
-
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.clear_flag('c');
-    cpu.update_flag('z', cpu.hl.high == 0);
+fn swap_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.hl.high = swap(cpu, cpu.hl.high);
+    2
 }
 
-fn swap_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lower_bits = cpu.hl.low & 0b1111;
-    let higher_bits = cpu.hl.low & 0b11110000;
-    cpu.hl.low = (higher_bits >> 4) + (lower_bits << 4);
-
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.clear_flag('c');
-    cpu.update_flag('z', cpu.hl.low == 0);
+fn swap_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.hl.low = swap(cpu, cpu.hl.low);
+    2
 }
 
-fn swap_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    let lower_bits = op & 0b1111;
-    let higher_bits = op & 0b11110000;
-    let new_op = (higher_bits >> 4) + (lower_bits << 4);
-    bus.set_byte(cpu.hl.get_combined(), new_op);
-
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.clear_flag('c');
-    cpu.update_flag('z', new_op == 0);
+fn swap_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let address = cpu.hl.get_combined();
+    let op = cpu.read_byte(bus, address);
+    let result = swap(cpu, op);
+    cpu.write_byte(bus, address, result);
+    4
 }
 
-fn swap_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lower_bits = cpu.af.high & 0b1111;
-    let higher_bits = cpu.af.high & 0b11110000;
-    cpu.af.high = (higher_bits >> 4) + (lower_bits << 4);
-
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.clear_flag('c');
-    cpu.update_flag('z', cpu.af.high == 0);
+fn swap_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.af.high = swap(cpu, cpu.af.high);
+    2
 }
 
-fn srl_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lowest_bit = cpu.bc.high & 1;
-    cpu.update_flag('c', lowest_bit != 0);
-    cpu.bc.high = (cpu.bc.high >> 1) & 0b01111111;
-
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', cpu.bc.high == 0);
+fn srl_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.bc.high = srl(cpu, cpu.bc.high);
+    2
 }
 
-fn srl_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lowest_bit = cpu.bc.low & 1;
-    cpu.update_flag('c', lowest_bit != 0);
-    cpu.bc.low = (cpu.bc.low >> 1) & 0b01111111;
-
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', cpu.bc.low == 0);
+fn srl_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.bc.low = srl(cpu, cpu.bc.low);
+    2
 }
 
-fn srl_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lowest_bit = cpu.de.high & 1;
-    cpu.update_flag('c', lowest_bit != 0);
-    cpu.de.high = (cpu.de.high >> 1) & 0b01111111;
-
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', cpu.de.high == 0);
+fn srl_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.de.high = srl(cpu, cpu.de.high);
+    2
 }
 
-fn srl_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lowest_bit = cpu.de.low & 1;
-    cpu.update_flag('c', lowest_bit != 0);
-    cpu.de.low = (cpu.de.low >> 1) & 0b01111111;
-
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', cpu.de.low == 0);
+fn srl_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.de.low = srl(cpu, cpu.de.low);
+    2
 }
 
-fn srl_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lowest_bit = cpu.hl.high & 1;
-    cpu.update_flag('c', lowest_bit != 0);
-    cpu.hl.high = (cpu.hl.high >> 1) & 0b01111111;
-
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', cpu.hl.high == 0);
+fn srl_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.hl.high = srl(cpu, cpu.hl.high);
+    2
 }
 
-fn srl_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lowest_bit = cpu.hl.low & 1;
-    cpu.update_flag('c', lowest_bit != 0);
-    cpu.hl.low = (cpu.hl.low >> 1) & 0b01111111;
-
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', cpu.hl.low == 0);
+fn srl_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.hl.low = srl(cpu, cpu.hl.low);
+    2
 }
 
-fn srl_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    let lowest_bit = op & 1;
-    cpu.update_flag('c', lowest_bit != 0);
-    let new_op = (op >> 1) & 0b01111111;
-    bus.set_byte(cpu.hl.get_combined(), new_op);
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', new_op == 0);
+fn srl_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let address = cpu.hl.get_combined();
+    let op = cpu.read_byte(bus, address);
+    let result = srl(cpu, op);
+    cpu.write_byte(bus, address, result);
+    4
 }
 
-fn srl_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let lowest_bit = cpu.af.high & 1;
-    cpu.update_flag('c', lowest_bit != 0);
-    cpu.af.high = (cpu.af.high >> 1) & 0b01111111;
-
-    cpu.clear_flag('h');
-    cpu.clear_flag('n');
-    cpu.update_flag('z', cpu.af.high == 0);
+fn srl_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.af.high = srl(cpu, cpu.af.high);
+    2
 }
 
 // ======================================================
 // 0x4X Instructions
 // ======================================================
-fn bit_0_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.bc.high & 1;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_0_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.bc.high, 0);
+    2
 }
 
-fn bit_0_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.bc.low & 1;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_0_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.bc.low, 0);
+    2
 }
 
-fn bit_0_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.de.high & 1;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_0_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.de.high, 0);
+    2
 }
 
-fn bit_0_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.de.low & 1;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_0_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.de.low, 0);
+    2
 }
 
-fn bit_0_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.hl.high & 1;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_0_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.hl.high, 0);
+    2
 }
 
-fn bit_0_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.hl.low & 1;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_0_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.hl.low, 0);
+    2
 }
 
-fn bit_0_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    let bit = op & 1;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_0_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    test_bit(cpu, op, 0);
+    3
 }
 
-fn bit_0_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.af.high & 1;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_0_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.af.high, 0);
+    2
 }
 
-fn bit_1_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.bc.high & 0b10;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_1_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.bc.high, 1);
+    2
 }
 
-fn bit_1_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.bc.low & 0b10;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_1_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.bc.low, 1);
+    2
 }
 
-fn bit_1_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.de.high & 0b10;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_1_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.de.high, 1);
+    2
 }
 
-fn bit_1_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.de.low & 0b10;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_1_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.de.low, 1);
+    2
 }
 
-fn bit_1_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.hl.high & 0b10;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_1_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.hl.high, 1);
+    2
 }
 
-fn bit_1_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.hl.low & 0b10;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_1_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.hl.low, 1);
+    2
 }
 
-fn bit_1_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    let bit = op & 0b10;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_1_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    test_bit(cpu, op, 1);
+    3
 }
 
-fn bit_1_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.af.high & 0b10;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_1_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.af.high, 1);
+    2
 }
 
 // ======================================================
 // 0x5X Instructions
 // ======================================================
-fn bit_2_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.bc.high & 0b100;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_2_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.bc.high, 2);
+    2
 }
 
-fn bit_2_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.bc.low & 0b100;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_2_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.bc.low, 2);
+    2
 }
 
-fn bit_2_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.de.high & 0b100;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_2_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.de.high, 2);
+    2
 }
 
-fn bit_2_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.de.low & 0b100;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_2_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.de.low, 2);
+    2
 }
 
-fn bit_2_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.hl.high & 0b100;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_2_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.hl.high, 2);
+    2
 }
 
-fn bit_2_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.hl.low & 0b100;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_2_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.hl.low, 2);
+    2
 }
 
-fn bit_2_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    let bit = op & 0b100;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_2_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    test_bit(cpu, op, 2);
+    3
 }
 
-fn bit_2_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.af.high & 0b100;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_2_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.af.high, 2);
+    2
 }
 
-fn bit_3_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.bc.high & 0b1000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_3_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.bc.high, 3);
+    2
 }
 
-fn bit_3_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.bc.low & 0b1000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_3_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.bc.low, 3);
+    2
 }
 
-fn bit_3_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.de.high & 0b1000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_3_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.de.high, 3);
+    2
 }
 
-fn bit_3_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.de.low & 0b1000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_3_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.de.low, 3);
+    2
 }
 
-fn bit_3_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.hl.high & 0b1000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_3_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.hl.high, 3);
+    2
 }
 
-fn bit_3_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.hl.low & 0b1000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_3_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.hl.low, 3);
+    2
 }
 
-fn bit_3_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    let bit = op & 0b1000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_3_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    test_bit(cpu, op, 3);
+    3
 }
 
-fn bit_3_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.af.high & 0b1000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_3_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.af.high, 3);
+    2
 }
 
 // ======================================================
 // 0x6X Instructions
 // ======================================================
-fn bit_4_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.bc.high & 0b10000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_4_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.bc.high, 4);
+    2
 }
 
-fn bit_4_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.bc.low & 0b10000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_4_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.bc.low, 4);
+    2
 }
 
-fn bit_4_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.de.high & 0b10000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_4_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.de.high, 4);
+    2
 }
 
-fn bit_4_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.de.low & 0b10000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_4_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.de.low, 4);
+    2
 }
 
-fn bit_4_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.hl.high & 0b10000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_4_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.hl.high, 4);
+    2
 }
 
-fn bit_4_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.hl.low & 0b10000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_4_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.hl.low, 4);
+    2
 }
 
-fn bit_4_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    let bit = op & 0b10000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_4_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    test_bit(cpu, op, 4);
+    3
 }
 
-fn bit_4_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.af.high & 0b10000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_4_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.af.high, 4);
+    2
 }
 
-fn bit_5_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.bc.high & 0b100000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_5_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.bc.high, 5);
+    2
 }
 
-fn bit_5_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.bc.low & 0b100000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_5_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.bc.low, 5);
+    2
 }
 
-fn bit_5_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.de.high & 0b100000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_5_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.de.high, 5);
+    2
 }
 
-fn bit_5_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.de.low & 0b100000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_5_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.de.low, 5);
+    2
 }
 
-fn bit_5_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.hl.high & 0b100000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_5_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.hl.high, 5);
+    2
 }
 
-fn bit_5_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.hl.low & 0b100000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_5_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.hl.low, 5);
+    2
 }
 
-fn bit_5_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    let bit = op & 0b100000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_5_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    test_bit(cpu, op, 5);
+    3
 }
 
-fn bit_5_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.af.high & 0b100000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_5_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.af.high, 5);
+    2
 }
 
 // ======================================================
 // 0x7X Instructions
 // ======================================================
-fn bit_6_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.bc.high & 0b100000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_6_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.bc.high, 6);
+    2
 }
 
-fn bit_6_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.bc.low & 0b100000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_6_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.bc.low, 6);
+    2
 }
 
-fn bit_6_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.de.high & 0b100000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_6_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.de.high, 6);
+    2
 }
 
-fn bit_6_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.de.low & 0b100000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_6_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.de.low, 6);
+    2
 }
 
-fn bit_6_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.hl.high & 0b100000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_6_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.hl.high, 6);
+    2
 }
 
-fn bit_6_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.hl.low & 0b100000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_6_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.hl.low, 6);
+    2
 }
 
-fn bit_6_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    let bit = op & 0b100000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_6_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    test_bit(cpu, op, 6);
+    3
 }
 
-fn bit_6_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.af.high & 0b100000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_6_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.af.high, 6);
+    2
 }
 
-fn bit_7_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.bc.high & 0b1000000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_7_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.bc.high, 7);
+    2
 }
 
-fn bit_7_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.bc.low & 0b1000000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_7_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.bc.low, 7);
+    2
 }
 
-fn bit_7_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.de.high & 0b1000000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_7_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.de.high, 7);
+    2
 }
 
-fn bit_7_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.de.low & 0b1000000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_7_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.de.low, 7);
+    2
 }
 
-fn bit_7_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.hl.high & 0b1000000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_7_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.hl.high, 7);
+    2
 }
 
-fn bit_7_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.hl.low & 0b1000000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_7_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.hl.low, 7);
+    2
 }
 
-fn bit_7_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    let bit = op & 0b1000000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_7_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    test_bit(cpu, op, 7);
+    3
 }
 
-fn bit_7_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    let bit = cpu.af.high & 0b1000000;
-
-    cpu.clear_flag('n');
-    cpu.set_flag('h');
-    cpu.update_flag('z', bit == 0);
+fn bit_7_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    test_bit(cpu, cpu.af.high, 7);
+    2
 }
 
 // ======================================================
 // 0x8X Instructions
 // ======================================================
-fn res_0_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_0_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.high &= 0b11111110;
+    2
 }
 
-fn res_0_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_0_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.low &= 0b11111110;
+    2
 }
 
-fn res_0_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_0_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.high &= 0b11111110;
+    2
 }
 
-fn res_0_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_0_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.low &= 0b11111110;
+    2
 }
 
-fn res_0_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_0_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.high &= 0b11111110;
+    2
 }
 
-fn res_0_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_0_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.low &= 0b11111110;
+    2
 }
 
-fn res_0_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    bus.set_byte(cpu.hl.get_combined(), op & 0b11111110);
+fn res_0_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    cpu.write_byte(bus, cpu.hl.get_combined(), op & 0b11111110);
+    4
 }
 
-fn res_0_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_0_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.af.high &= 0b11111110;
+    2
 }
 
-fn res_1_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_1_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.high &= 0b11111101;
+    2
 }
 
-fn res_1_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_1_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.low &= 0b11111101;
+    2
 }
 
-fn res_1_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_1_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.high &= 0b11111101;
+    2
 }
 
-fn res_1_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_1_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.low &= 0b11111101;
+    2
 }
 
-fn res_1_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_1_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.high &= 0b11111101;
+    2
 }
 
-fn res_1_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_1_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.low &= 0b11111101;
+    2
 }
 
-fn res_1_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    bus.set_byte(cpu.hl.get_combined(), op & 0b11111101);
+fn res_1_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    cpu.write_byte(bus, cpu.hl.get_combined(), op & 0b11111101);
+    4
 }
 
-fn res_1_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_1_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.af.high &= 0b11111101;
+    2
 }
 
 // ======================================================
 // 0x9X Instructions
 // ======================================================
-fn res_2_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_2_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.high &= 0b11111011;
+    2
 }
 
-fn res_2_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_2_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.low &= 0b11111011;
+    2
 }
 
-fn res_2_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_2_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.high &= 0b11111011;
+    2
 }
 
-fn res_2_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_2_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.low &= 0b11111011;
+    2
 }
 
-fn res_2_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_2_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.high &= 0b11111011;
+    2
 }
 
-fn res_2_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_2_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.low &= 0b11111011;
+    2
 }
 
-fn res_2_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    bus.set_byte(cpu.hl.get_combined(), op & 0b11111011);
+fn res_2_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    cpu.write_byte(bus, cpu.hl.get_combined(), op & 0b11111011);
+    4
 }
 
-fn res_2_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_2_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.af.high &= 0b11111011;
+    2
 }
 
-fn res_3_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_3_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.high &= 0b11110111;
+    2
 }
 
-fn res_3_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_3_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.low &= 0b11110111;
+    2
 }
 
-fn res_3_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_3_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.high &= 0b11110111;
+    2
 }
 
-fn res_3_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_3_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.low &= 0b11110111;
+    2
 }
 
-fn res_3_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_3_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.high &= 0b11110111;
+    2
 }
 
-fn res_3_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_3_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.low &= 0b11110111;
+    2
 }
 
-fn res_3_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    bus.set_byte(cpu.hl.get_combined(), op & 0b11110111);
+fn res_3_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    cpu.write_byte(bus, cpu.hl.get_combined(), op & 0b11110111);
+    4
 }
 
-fn res_3_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_3_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.af.high &= 0b11110111;
+    2
 }
 
 // ======================================================
 // 0xAX Instructions
 // ======================================================
-fn res_4_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_4_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.high &= 0b11101111;
+    2
 }
 
-fn res_4_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_4_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.low &= 0b11101111;
+    2
 }
 
-fn res_4_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_4_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.high &= 0b11101111;
+    2
 }
 
-fn res_4_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_4_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.low &= 0b11101111;
+    2
 }
 
-fn res_4_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_4_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.high &= 0b11101111;
+    2
 }
 
-fn res_4_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_4_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.low &= 0b11101111;
+    2
 }
 
-fn res_4_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    bus.set_byte(cpu.hl.get_combined(), op & 0b11101111);
+fn res_4_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    cpu.write_byte(bus, cpu.hl.get_combined(), op & 0b11101111);
+    4
 }
 
-fn res_4_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_4_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.af.high &= 0b11101111;
+    2
 }
 
-fn res_5_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_5_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.high &= 0b11011111;
+    2
 }
 
-fn res_5_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_5_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.low &= 0b11011111;
+    2
 }
 
-fn res_5_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_5_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.high &= 0b11011111;
+    2
 }
 
-fn res_5_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_5_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.low &= 0b11011111;
+    2
 }
 
-fn res_5_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_5_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.high &= 0b11011111;
+    2
 }
 
-fn res_5_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_5_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.low &= 0b11011111;
+    2
 }
 
-fn res_5_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    bus.set_byte(cpu.hl.get_combined(), op & 0b11011111);
+fn res_5_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    cpu.write_byte(bus, cpu.hl.get_combined(), op & 0b11011111);
+    4
 }
 
-fn res_5_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_5_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.af.high &= 0b11011111;
+    2
 }
 
 // ======================================================
 // 0xBX Instructions
 // ======================================================
-fn res_6_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_6_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.high &= 0b10111111;
+    2
 }
 
-fn res_6_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_6_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.low &= 0b10111111;
+    2
 }
 
-fn res_6_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_6_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.high &= 0b10111111;
+    2
 }
 
-fn res_6_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_6_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.low &= 0b10111111;
+    2
 }
 
-fn res_6_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_6_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.high &= 0b10111111;
+    2
 }
 
-fn res_6_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_6_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.low &= 0b10111111;
+    2
 }
 
-fn res_6_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    bus.set_byte(cpu.hl.get_combined(), op & 0b10111111);
+fn res_6_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    cpu.write_byte(bus, cpu.hl.get_combined(), op & 0b10111111);
+    4
 }
 
-fn res_6_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_6_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.af.high &= 0b10111111;
+    2
 }
 
-fn res_7_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_7_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.high &= 0b01111111;
+    2
 }
 
-fn res_7_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_7_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.low &= 0b01111111;
+    2
 }
 
-fn res_7_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_7_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.high &= 0b01111111;
+    2
 }
 
-fn res_7_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_7_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.low &= 0b01111111;
+    2
 }
 
-fn res_7_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_7_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.high &= 0b01111111;
+    2
 }
 
-fn res_7_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_7_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.low &= 0b01111111;
+    2
 }
 
-fn res_7_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    bus.set_byte(cpu.hl.get_combined(), op & 0b01111111);
+fn res_7_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    cpu.write_byte(bus, cpu.hl.get_combined(), op & 0b01111111);
+    4
 }
 
-fn res_7_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn res_7_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.af.high &= 0b01111111;
+    2
 }
 
 // ======================================================
 // 0xCX Instructions
 // ======================================================
-fn set_0_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.bc.high |= 1;
+fn set_0_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.bc.high |= 0b1;
+    2
 }
 
-fn set_0_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.bc.low |= 1;
+fn set_0_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.bc.low |= 0b1;
+    2
 }
 
-fn set_0_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.de.high |= 1;
+fn set_0_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.de.high |= 0b1;
+    2
 }
 
-fn set_0_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.de.low |= 1;
+fn set_0_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.de.low |= 0b1;
+    2
 }
 
-fn set_0_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.hl.high |= 1;
+fn set_0_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.hl.high |= 0b1;
+    2
 }
 
-fn set_0_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.hl.low |= 1;
+fn set_0_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.hl.low |= 0b1;
+    2
 }
 
-fn set_0_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    bus.set_byte(cpu.hl.get_combined(), op | 1);
+fn set_0_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    cpu.write_byte(bus, cpu.hl.get_combined(), op | 0b1);
+    4
 }
 
-fn set_0_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.af.low |= 1;
+fn set_0_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.af.high |= 0b1;
+    2
 }
 
-fn set_1_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_1_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.high |= 0b10;
+    2
 }
 
-fn set_1_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_1_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.low |= 0b10;
+    2
 }
 
-fn set_1_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_1_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.high |= 0b10;
+    2
 }
 
-fn set_1_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_1_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.low |= 0b10;
+    2
 }
 
-fn set_1_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_1_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.high |= 0b10;
+    2
 }
 
-fn set_1_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_1_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.low |= 0b10;
+    2
 }
 
-fn set_1_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    bus.set_byte(cpu.hl.get_combined(), op | 0b10);
+fn set_1_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    cpu.write_byte(bus, cpu.hl.get_combined(), op | 0b10);
+    4
 }
 
-fn set_1_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.af.low |= 0b10;
+fn set_1_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.af.high |= 0b10;
+    2
 }
 
 // ======================================================
 // 0xDX Instructions
 // ======================================================
-fn set_2_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_2_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.high |= 0b100;
+    2
 }
 
-fn set_2_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_2_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.low |= 0b100;
+    2
 }
 
-fn set_2_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_2_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.high |= 0b100;
+    2
 }
 
-fn set_2_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_2_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.low |= 0b100;
+    2
 }
 
-fn set_2_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_2_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.high |= 0b100;
+    2
 }
 
-fn set_2_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_2_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.low |= 0b100;
+    2
 }
 
-fn set_2_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    bus.set_byte(cpu.hl.get_combined(), op | 0b100);
+fn set_2_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    cpu.write_byte(bus, cpu.hl.get_combined(), op | 0b100);
+    4
 }
 
-fn set_2_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.af.low |= 0b100;
+fn set_2_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.af.high |= 0b100;
+    2
 }
 
-fn set_3_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_3_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.high |= 0b1000;
+    2
 }
 
-fn set_3_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_3_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.low |= 0b1000;
+    2
 }
 
-fn set_3_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_3_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.high |= 0b1000;
+    2
 }
 
-fn set_3_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_3_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.low |= 0b1000;
+    2
 }
 
-fn set_3_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_3_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.high |= 0b1000;
+    2
 }
 
-fn set_3_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_3_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.low |= 0b1000;
+    2
 }
 
-fn set_3_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    bus.set_byte(cpu.hl.get_combined(), op | 0b1000);
+fn set_3_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    cpu.write_byte(bus, cpu.hl.get_combined(), op | 0b1000);
+    4
 }
 
-fn set_3_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.af.low |= 0b1000;
+fn set_3_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.af.high |= 0b1000;
+    2
 }
 
 // ======================================================
 // 0xEX Instructions
 // ======================================================
-fn set_4_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_4_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.high |= 0b10000;
+    2
 }
 
-fn set_4_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_4_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.low |= 0b10000;
+    2
 }
 
-fn set_4_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_4_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.high |= 0b10000;
+    2
 }
 
-fn set_4_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_4_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.low |= 0b10000;
+    2
 }
 
-fn set_4_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_4_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.high |= 0b10000;
+    2
 }
 
-fn set_4_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_4_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.low |= 0b10000;
+    2
 }
 
-fn set_4_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    bus.set_byte(cpu.hl.get_combined(), op | 0b10000);
+fn set_4_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    cpu.write_byte(bus, cpu.hl.get_combined(), op | 0b10000);
+    4
 }
 
-fn set_4_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.af.low |= 0b10000;
+fn set_4_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.af.high |= 0b10000;
+    2
 }
 
-fn set_5_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_5_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.high |= 0b100000;
+    2
 }
 
-fn set_5_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_5_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.low |= 0b100000;
+    2
 }
 
-fn set_5_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_5_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.high |= 0b100000;
+    2
 }
 
-fn set_5_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_5_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.low |= 0b100000;
+    2
 }
 
-fn set_5_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_5_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.high |= 0b100000;
+    2
 }
 
-fn set_5_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_5_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.low |= 0b100000;
+    2
 }
 
-fn set_5_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    bus.set_byte(cpu.hl.get_combined(), op | 0b100000);
+fn set_5_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    cpu.write_byte(bus, cpu.hl.get_combined(), op | 0b100000);
+    4
 }
 
-fn set_5_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.af.low |= 0b100000;
+fn set_5_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.af.high |= 0b100000;
+    2
 }
 
 // ======================================================
 // 0xFX Instructions
 // ======================================================
-fn set_6_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_6_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.high |= 0b1000000;
+    2
 }
 
-fn set_6_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_6_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.low |= 0b1000000;
+    2
 }
 
-fn set_6_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_6_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.high |= 0b1000000;
+    2
 }
 
-fn set_6_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_6_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.low |= 0b1000000;
+    2
 }
 
-fn set_6_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_6_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.high |= 0b1000000;
+    2
 }
 
-fn set_6_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_6_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.low |= 0b1000000;
+    2
 }
 
-fn set_6_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    bus.set_byte(cpu.hl.get_combined(), op | 0b1000000);
+fn set_6_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    cpu.write_byte(bus, cpu.hl.get_combined(), op | 0b1000000);
+    4
 }
 
-fn set_6_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.af.low |= 0b1000000;
+fn set_6_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.af.high |= 0b1000000;
+    2
 }
 
-fn set_7_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_7_b(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.high |= 0b10000000;
+    2
 }
 
-fn set_7_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_7_c(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.bc.low |= 0b10000000;
+    2
 }
 
-fn set_7_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_7_d(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.high |= 0b10000000;
+    2
 }
 
-fn set_7_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_7_e(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.de.low |= 0b10000000;
+    2
 }
 
-fn set_7_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_7_h(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.high |= 0b10000000;
+    2
 }
 
-fn set_7_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
+fn set_7_l(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
     cpu.hl.low |= 0b10000000;
+    2
 }
 
-fn set_7_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) {
-    let op = bus.fetch_byte(cpu.hl.get_combined());
-    bus.set_byte(cpu.hl.get_combined(), op | 0b10000000);
+fn set_7_hl_ptr(cpu: &mut cpu::CPU, bus: &mut bus::Bus) -> u8 {
+    let op = cpu.read_byte(bus, cpu.hl.get_combined());
+    cpu.write_byte(bus, cpu.hl.get_combined(), op | 0b10000000);
+    4
 }
 
-fn set_7_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) {
-    cpu.af.low |= 0b10000000;
-}
\ No newline at end of file
+fn set_7_a(cpu: &mut cpu::CPU, _: &mut bus::Bus) -> u8 {
+    cpu.af.high |= 0b10000000;
+    2
+}
+
+// ======================================================
+// Shared operations
+// ======================================================
+fn rlc(cpu: &mut cpu::CPU, value: u8) -> u8 {
+    // rotate left, bit 7 goes to bit 0 and to the carry flag
+    let result = value.rotate_left(1);
+    set_shift_flags(cpu, result, value & 0b10000000 != 0);
+    result
+}
+
+fn rrc(cpu: &mut cpu::CPU, value: u8) -> u8 {
+    // rotate right, bit 0 goes to bit 7 and to the carry flag
+    let result = value.rotate_right(1);
+    set_shift_flags(cpu, result, value & 1 != 0);
+    result
+}
+
+fn rl(cpu: &mut cpu::CPU, value: u8) -> u8 {
+    // rotate left through the carry flag
+    let result = (value << 1) | (cpu.extract_flag('c') as u8);
+    set_shift_flags(cpu, result, value & 0b10000000 != 0);
+    result
+}
+
+fn rr(cpu: &mut cpu::CPU, value: u8) -> u8 {
+    // rotate right through the carry flag
+    let result = (value >> 1) | ((cpu.extract_flag('c') as u8) << 7);
+    set_shift_flags(cpu, result, value & 1 != 0);
+    result
+}
+
+fn sla(cpu: &mut cpu::CPU, value: u8) -> u8 {
+    // arithmetic shift left, bit 0 is reset
+    let result = value << 1;
+    set_shift_flags(cpu, result, value & 0b10000000 != 0);
+    result
+}
+
+fn sra(cpu: &mut cpu::CPU, value: u8) -> u8 {
+    // arithmetic shift right, bit 7 is kept
+    let result = (value >> 1) | (value & 0b10000000);
+    set_shift_flags(cpu, result, value & 1 != 0);
+    result
+}
+
+fn swap(cpu: &mut cpu::CPU, value: u8) -> u8 {
+    // exchange the upper and lower nibbles
+    let result = value.rotate_left(4);
+    set_shift_flags(cpu, result, false);
+    result
+}
+
+fn srl(cpu: &mut cpu::CPU, value: u8) -> u8 {
+    // logical shift right, bit 7 is reset
+    let result = value >> 1;
+    set_shift_flags(cpu, result, value & 1 != 0);
+    result
+}
+
+fn set_shift_flags(cpu: &mut cpu::CPU, result: u8, carry: bool) {
+    cpu.update_flag('z', result == 0);
+    cpu.clear_flag('n');
+    cpu.clear_flag('h');
+    cpu.update_flag('c', carry);
+}
+
+fn test_bit(cpu: &mut cpu::CPU, value: u8, bit: u8) {
+    // Z is set if the bit is 0, C is not affected
+    cpu.update_flag('z', value & (1 << bit) == 0);
+    cpu.clear_flag('n');
+    cpu.set_flag('h');
+}
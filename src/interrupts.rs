@@ -0,0 +1,74 @@
+use crate::state;
+
+pub struct InterruptController {
+    enable: u8, // IE register
+    flags: u8, // IF register, only the 5 lower bits exist
+}
+
+impl InterruptController {
+    pub const VBLANK: u8 = 0b1;
+    pub const LCD_STAT: u8 = 0b10;
+    pub const TIMER: u8 = 0b100;
+    pub const SERIAL: u8 = 0b1000;
+    pub const JOYPAD: u8 = 0b10000;
+
+    pub const ENABLE_REGISTER: u16 = 0xFFFF;
+    pub const FLAG_REGISTER: u16 = 0xFF0F;
+
+    const INTERRUPT_MASK: u8 = 0b11111;
+
+    pub fn new_interrupt_controller() -> InterruptController {
+        InterruptController { enable: 0, flags: 0 }
+    }
+
+    pub fn request(&mut self, interrupt: u8) {
+        self.flags |= interrupt & InterruptController::INTERRUPT_MASK;
+    }
+
+    pub fn pending(&self) -> u8 {
+        // interrupts both enabled and requested
+        self.enable & self.flags & InterruptController::INTERRUPT_MASK
+    }
+
+    pub fn acknowledge(&mut self, interrupt: u8) {
+        self.flags &= !interrupt;
+    }
+
+    pub fn highest_priority(pending: u8) -> Option<(u8, u16)> {
+        // the interrupt with the lowest bit wins, returns it with the address of its handler
+        match pending & InterruptController::INTERRUPT_MASK {
+            0 => None,
+            pending => {
+                let bit = pending.trailing_zeros() as u16;
+                Some((1 << bit, 0x40 + 8 * bit))
+            },
+        }
+    }
+
+    pub fn fetch_byte(&self, address: u16) -> u8 {
+        match address {
+            InterruptController::FLAG_REGISTER => self.flags | 0b11100000, // unused bits read as 1
+            InterruptController::ENABLE_REGISTER => self.enable,
+            _ => panic!("Address {:#06x} is not an interrupt register !", address),
+        }
+    }
+
+    pub fn set_byte(&mut self, address: u16, data: u8) {
+        match address {
+            InterruptController::FLAG_REGISTER => self.flags = data & InterruptController::INTERRUPT_MASK,
+            InterruptController::ENABLE_REGISTER => self.enable = data, // all 8 bits are writable
+            _ => panic!("Address {:#06x} is not an interrupt register !", address),
+        }
+    }
+
+    pub fn save_state(&self, writer: &mut state::StateWriter) {
+        writer.write_u8(self.enable);
+        writer.write_u8(self.flags);
+    }
+
+    pub fn load_state(&mut self, reader: &mut state::StateReader) -> Result<(), String> {
+        self.enable = reader.read_u8()?;
+        self.flags = reader.read_u8()? & InterruptController::INTERRUPT_MASK;
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use crate::state;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Button {
+    Right,
+    Left,
+    Up,
+    Down,
+    A,
+    B,
+    Select,
+    Start,
+}
+
+pub struct Joypad {
+    row_1: u8, // direction keys, bits are 0 when pressed
+    row_2: u8, // button keys
+    select: u8, // bits 4 and 5 of P1, a row is selected when its bit is 0
+}
+
+impl Joypad {
+    pub const REGISTER: u16 = 0xFF00;
+
+    const SELECT_DIRECTIONS: u8 = 0b10000;
+    const SELECT_BUTTONS: u8 = 0b100000;
+
+    pub fn new_joypad() -> Joypad {
+        Joypad { row_1: 0xF, row_2: 0xF, select: 0b110000 }
+    }
+
+    pub fn save_state(&self, writer: &mut state::StateWriter) {
+        // the keys held are host input, only the register is part of the machine
+        writer.write_u8(self.select);
+    }
+
+    pub fn load_state(&mut self, reader: &mut state::StateReader) -> Result<(), String> {
+        self.select = reader.read_u8()? & 0b110000;
+        Ok(())
+    }
+
+    fn button_bit(button: Button) -> (bool, u8) {
+        // returns whether the button is on the direction row, and its bit in that row
+        match button {
+            Button::Right => (true, 0b1),
+            Button::Left => (true, 0b10),
+            Button::Up => (true, 0b100),
+            Button::Down => (true, 0b1000),
+            Button::A => (false, 0b1),
+            Button::B => (false, 0b10),
+            Button::Select => (false, 0b100),
+            Button::Start => (false, 0b1000),
+        }
+    }
+
+    fn lines(&self) -> u8 {
+        // the lines of every selected row are wired together, a line is low if a key is pressed in any of them
+        let mut lines = 0xF;
+        if self.select & Joypad::SELECT_DIRECTIONS == 0 {
+            lines &= self.row_1;
+        }
+        if self.select & Joypad::SELECT_BUTTONS == 0 {
+            lines &= self.row_2;
+        }
+        lines
+    }
+
+    fn line_fell(&self, previous_lines: u8) -> bool {
+        previous_lines & !self.lines() != 0
+    }
+
+    pub fn any_line_low(&self) -> bool {
+        self.lines() != 0xF
+    }
+
+    pub fn press(&mut self, button: Button) -> bool {
+        // returns true if the joypad interrupt must be requested, when a selected line goes from high to low
+        let previous_lines = self.lines();
+        match Joypad::button_bit(button) {
+            (true, bit) => self.row_1 &= !bit,
+            (false, bit) => self.row_2 &= !bit,
+        };
+        self.line_fell(previous_lines)
+    }
+
+    pub fn release(&mut self, button: Button) {
+        match Joypad::button_bit(button) {
+            (true, bit) => self.row_1 |= bit,
+            (false, bit) => self.row_2 |= bit,
+        };
+    }
+
+    pub fn fetch_byte(&self) -> u8 {
+        0b11000000 | self.select | self.lines() // unused bits read as 1
+    }
+
+    pub fn set_byte(&mut self, data: u8) -> bool {
+        // selecting a row where a key is held also pulls its line low, and requests the interrupt
+        let previous_lines = self.lines();
+        self.select = data & 0b110000;
+        self.line_fell(previous_lines)
+    }
+}
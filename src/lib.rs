@@ -0,0 +1,19 @@
+pub mod apu;
+pub mod bus;
+pub mod cartridge;
+pub mod cpu;
+pub mod gameboy;
+pub mod gpu;
+pub mod header;
+pub mod instructions;
+pub mod instructions2;
+pub mod interrupts;
+pub mod joypad;
+pub mod link;
+pub mod rtc;
+pub mod save;
+pub mod serial;
+pub mod state;
+pub mod timer;
+
+pub use gameboy::GameBoy;
@@ -0,0 +1,227 @@
+use crate::serial;
+
+use std::io::Read;
+use std::io::Write;
+use std::net::Shutdown;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+// link cable between two emulator processes over TCP
+// time is counted in shift clocks (8192 Hz) on both sides, and each side sends its time to the other one regularly
+// the side driving a transfer sends its byte with its time t and waits for the reply, the other side receives it
+// and replies with its own byte at exactly t + LATENCY ; a side may still drive a transfer at the time it just sent,
+// so to know there is no transfer to receive, a side only reaches time T once the other one passed T - LATENCY,
+// which keeps both in lockstep and the exchanges deterministic
+
+enum Message {
+    Clock(u64), // the sender reached this time
+    Transfer(u8, u64), // the sender drives a transfer at this time
+    Reply(u8), // answer to a transfer
+}
+
+pub struct TcpLink {
+    stream: TcpStream,
+    messages: mpsc::Receiver<Message>, // filled by a thread reading the socket
+    connected: bool,
+    clock: u64,
+    peer_clock: u64, // last time received from the other side
+    sent_clock: u64, // last time sent to the other side
+    pending_transfer: Option<(u8, u64)>, // transfer driven by the other side, not received yet
+    wait_callback: Option<Box<dyn FnMut() -> bool>>, // called while waiting for the other side, false gives up
+}
+
+impl TcpLink {
+    const LATENCY: u64 = 64; // about half a frame
+    const SYNC_INTERVAL: u64 = 16;
+    const MESSAGE_SIZE: usize = 10; // tag, data byte and time
+    const WAIT_SLICE: Duration = Duration::from_millis(20);
+    const PEER_TIMEOUT: Duration = Duration::from_secs(10);
+
+    const CLOCK: u8 = 0;
+    const TRANSFER: u8 = 1;
+    const REPLY: u8 = 2;
+
+    pub fn host(port: u16) -> Result<TcpLink, String> {
+        // wait for the other side to join
+        TcpLink::accept(&TcpLink::listen(port)?)
+    }
+
+    pub fn listen(port: u16) -> Result<TcpListener, String> {
+        // port 0 lets the system pick a free one, see local_addr
+        TcpListener::bind(("127.0.0.1", port)).map_err(|err| format!("Could not listen on port {} : {}", port, err))
+    }
+
+    pub fn accept(listener: &TcpListener) -> Result<TcpLink, String> {
+        let (stream, _) = listener.accept().map_err(|err| format!("Could not accept the link connection : {}", err))?;
+        TcpLink::from_stream(stream)
+    }
+
+    pub fn join(address: &str) -> Result<TcpLink, String> {
+        // a port alone joins a host on this machine
+        let address = if address.contains(':') { address.to_string() } else { format!("127.0.0.1:{}", address) };
+        let stream = TcpStream::connect(&address).map_err(|err| format!("Could not connect to {} : {}", address, err))?;
+        TcpLink::from_stream(stream)
+    }
+
+    fn from_stream(stream: TcpStream) -> Result<TcpLink, String> {
+        stream.set_nodelay(true).map_err(|err| err.to_string())?; // messages are tiny and latency bound
+        let mut reader = stream.try_clone().map_err(|err| err.to_string())?;
+        let (sender, messages) = mpsc::channel();
+        thread::spawn(move || {
+            let mut buffer = [0u8; TcpLink::MESSAGE_SIZE];
+            while reader.read_exact(&mut buffer).is_ok() {
+                let mut time = [0u8; 8];
+                time.copy_from_slice(&buffer[2..]);
+                let time = u64::from_le_bytes(time);
+                let message = match buffer[0] {
+                    TcpLink::CLOCK => Message::Clock(time),
+                    TcpLink::TRANSFER => Message::Transfer(buffer[1], time),
+                    _ => Message::Reply(buffer[1]),
+                };
+                if sender.send(message).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(TcpLink {
+            stream,
+            messages,
+            connected: true,
+            clock: 0,
+            peer_clock: 0,
+            sent_clock: 0,
+            pending_transfer: None,
+            wait_callback: None,
+        })
+    }
+
+    pub fn set_wait_callback(&mut self, callback: Box<dyn FnMut() -> bool>) {
+        // lets the frontend stay responsive while the other side is paused or gone
+        self.wait_callback = Some(callback);
+    }
+
+    fn send(&mut self, tag: u8, data: u8, time: u64) {
+        let mut buffer = [0u8; TcpLink::MESSAGE_SIZE];
+        buffer[0] = tag;
+        buffer[1] = data;
+        buffer[2..].copy_from_slice(&time.to_le_bytes());
+        if self.stream.write_all(&buffer).is_err() {
+            self.disconnect();
+        }
+    }
+
+    fn send_clock(&mut self) {
+        self.send(TcpLink::CLOCK, 0, self.clock);
+        self.sent_clock = self.clock;
+    }
+
+    fn disconnect(&mut self) {
+        // keep running as if the cable was unplugged
+        if self.connected {
+            eprintln!("Warning : the link cable was disconnected");
+        }
+        self.connected = false;
+        self.pending_transfer = None;
+    }
+
+    fn handle(&mut self, message: Message) {
+        match message {
+            Message::Clock(time) => self.peer_clock = time,
+            Message::Transfer(data, time) => {
+                self.peer_clock = time;
+                self.pending_transfer = Some((data, time));
+            },
+            Message::Reply(_) => {}, // only expected while driving a transfer
+        }
+    }
+
+    fn receive(&mut self) -> Option<Message> {
+        // wait for the next message of the other side, None once disconnected
+        let start = Instant::now();
+        loop {
+            match self.messages.recv_timeout(TcpLink::WAIT_SLICE) {
+                Ok(message) => return Some(message),
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    let keep_waiting = self.wait_callback.as_mut().is_none_or(|callback| callback());
+                    if !keep_waiting {
+                        break;
+                    }
+                    if start.elapsed() >= TcpLink::PEER_TIMEOUT {
+                        eprintln!("Warning : the other side of the link did not answer for {} seconds", TcpLink::PEER_TIMEOUT.as_secs());
+                        break;
+                    }
+                },
+            }
+        }
+        self.disconnect();
+        None
+    }
+}
+
+impl Drop for TcpLink {
+    fn drop(&mut self) {
+        // the reading thread holds a clone of the socket, which would keep the connection open
+        self.stream.shutdown(Shutdown::Both).ok();
+    }
+}
+
+impl serial::LinkEndpoint for TcpLink {
+    fn exchange(&mut self, data: u8) -> u8 {
+        if !self.connected {
+            return 0xFF;
+        }
+        if self.pending_transfer.take().is_some() {
+            // both sides drive the clock, neither reads the other one
+            self.send(TcpLink::REPLY, 0xFF, 0);
+        }
+        self.send(TcpLink::TRANSFER, data, self.clock);
+        self.sent_clock = self.clock;
+        self.clock += 8; // poll_external is not called while the byte is shifted
+        loop {
+            match self.receive() {
+                Some(Message::Reply(received)) => return received,
+                Some(Message::Transfer(_, time)) => {
+                    self.peer_clock = time;
+                    self.send(TcpLink::REPLY, 0xFF, 0);
+                },
+                Some(message) => self.handle(message),
+                None => return 0xFF,
+            }
+        }
+    }
+
+    fn poll_external(&mut self, data: u8) -> Option<u8> {
+        if !self.connected {
+            return None;
+        }
+        self.clock += 1;
+        if self.clock - self.sent_clock >= TcpLink::SYNC_INTERVAL {
+            self.send_clock();
+        }
+        loop {
+            // the other side is blocked until its transfer is answered, it must be done while waiting for it
+            if let Some((received, time)) = self.pending_transfer {
+                if time + TcpLink::LATENCY <= self.clock {
+                    self.pending_transfer = None;
+                    self.send(TcpLink::REPLY, data, 0);
+                    return Some(received);
+                }
+            }
+            // wait until everything the other side did up to LATENCY shift clocks ago is known
+            if !self.connected || self.clock < self.peer_clock + TcpLink::LATENCY {
+                return None;
+            }
+            if self.sent_clock != self.clock {
+                self.send_clock();
+            }
+            if let Some(message) = self.receive() {
+                self.handle(message);
+            }
+        }
+    }
+}
@@ -1,117 +1,341 @@
-mod bus;
-mod cpu;
-mod instructions;
-mod instructions2;
-mod gpu;
+mod bindings;
+mod cli;
 mod debugger;
 
-//use std::time::Duration;
+use std::cell::RefCell;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process;
+use std::rc::Rc;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use gbemulator::apu;
+use gbemulator::gpu;
+use gbemulator::link;
+use gbemulator::save;
+use gbemulator::serial;
+use gbemulator::GameBoy;
 
-use sdl2;
 use sdl2::event::Event;
 use sdl2::keyboard::Keycode;
+use sdl2::keyboard::Mod;
+
+const FRAME_DURATION: Duration = Duration::from_nanos(16_742_706); // 70224 clocks at 4194304 Hz, about 59.7 frames per second
+const MAX_FRAME_DELAY: Duration = Duration::from_millis(100); // running later than this, give up catching up
 
-pub struct Keys {
-    row_1: u8,
-    row_2: u8,
+fn update_keys(gameboy: &mut GameBoy, bindings: &bindings::Bindings, event: Event) {
+    match event {
+        Event::KeyDown { keycode: Some(val), .. } => {
+            if let Some(button) = bindings.key_button(val) {
+                gameboy.press(button);
+            }
+        },
+        Event::KeyUp { keycode: Some(val), .. } => {
+            if let Some(button) = bindings.key_button(val) {
+                gameboy.release(button);
+            }
+        },
+        Event::ControllerButtonDown { button, .. } => {
+            if let Some(button) = bindings.pad_button(button) {
+                gameboy.press(button);
+            }
+        },
+        Event::ControllerButtonUp { button, .. } => {
+            if let Some(button) = bindings.pad_button(button) {
+                gameboy.release(button);
+            }
+        },
+        _ => (),
+    };
 }
 
-impl Keys {
-    pub fn new_keys() -> Keys {
-        Keys { row_1: 0xF, row_2: 0xF }
+fn keycode_to_state_slot(key: Keycode) -> Option<u8> {
+    match key {
+        Keycode::F2 => Some(1),
+        Keycode::F3 => Some(2),
+        Keycode::F4 => Some(3),
+        Keycode::F5 => Some(4),
+        Keycode::F6 => Some(5),
+        Keycode::F7 => Some(6),
+        Keycode::F8 => Some(7),
+        Keycode::F9 => Some(8),
+        _ => None,
     }
+}
 
-    fn key_down(&mut self, key: Keycode) {
-        match key {
-            Keycode::Down => self.row_1 &= 0b11110111,
-            Keycode::Up => self.row_1 &= 0b11111011,
-            Keycode::Left => self.row_1 &= 0b11111101,
-            Keycode::Right => self.row_1 &= 0b11111110,
-            Keycode::Return => self.row_2 &= 0b11110111, // Start Button
-            Keycode::Space => self.row_2 &= 0b11111011, // Select Button
-            Keycode::B => self.row_2 &= 0b11111101,
-            Keycode::A => self.row_2 &= 0b11111110,
-            _ => (),
-        };
-    }
+fn save_state_slot(gameboy: &GameBoy, options: &cli::Options, slot: u8) -> Result<(), String> {
+    let path = save::state_path(&options.rom_path, options.save_dir.as_deref(), slot);
+    save::write_save(&path, &gameboy.save_state())?;
+    println!("Saved state to slot {}", slot);
+    Ok(())
+}
 
-    fn key_up(&mut self, key: Keycode) {
-        match key {
-            Keycode::Down => self.row_1 |= 0b1000,
-            Keycode::Up => self.row_1 |= 0b100,
-            Keycode::Left => self.row_1 |= 0b10,
-            Keycode::Right => self.row_1 |= 0b1,
-            Keycode::Return => self.row_2 |= 0b1000, // Start Button
-            Keycode::Space => self.row_2 |= 0b100, // Select Button
-            Keycode::B => self.row_2 |= 0b10,
-            Keycode::A => self.row_2 |= 0b1,
-            _ => (),
-        };
-    }
+fn load_state_slot(gameboy: &mut GameBoy, options: &cli::Options, slot: u8) -> Result<(), String> {
+    let path = save::state_path(&options.rom_path, options.save_dir.as_deref(), slot);
+    let data = fs::read(&path).map_err(|err| format!("Could not read save state {} : {}", path.display(), err))?;
+    gameboy.load_state(&data)?;
+    println!("Loaded state from slot {}", slot);
+    Ok(())
+}
 
-    pub fn update_keys(&mut self, event: sdl2::event::Event) {
-        match event {
-            Event::KeyDown { keycode: Some(val), .. } => self.key_down(val),
-            Event::KeyUp { keycode: Some(val), .. } => self.key_up(val),
-            _ => (),
-        };
+fn render_canvas(gameboy: &GameBoy, canvas: &mut sdl2::render::Canvas<sdl2::video::Window>, texture: &mut sdl2::render::Texture) -> Result<(), String> {
+    // upload the whole frame at once, the texture is stretched to the window
+    let width = gpu::GPU::SCREEN_WIDTH as usize;
+    texture.with_lock(None, |buffer: &mut [u8], pitch: usize| {
+        for (i, shade) in gameboy.framebuffer().iter().enumerate() {
+            let color = gpu::GPU::SHADE_COLORS[*shade as usize];
+            let offset = (i / width) * pitch + (i % width) * 3;
+            buffer[offset] = (color >> 16) as u8;
+            buffer[offset + 1] = (color >> 8) as u8;
+            buffer[offset + 2] = color as u8;
+        }
+    })?;
+    canvas.copy(texture, None, None)?;
+    canvas.present();
+    Ok(())
+}
+
+fn report_events(gameboy: &mut GameBoy) {
+    if let Some(event) = gameboy.take_event() {
+        eprintln!("Warning : {}", event.describe());
     }
+}
 
-    pub fn update_register(&self, bus: &mut bus::Bus) {
-        let row = (bus.fetch_byte(0xFF00) & 0b110000) >> 4;
-        if row & 1 == 0 { // 4 upper bits are set to 1 to keep from reading more values
-            bus.set_byte(0xFF00, (self.row_1 & 0xF) | 0b11110000); // update register with direction keys values
-        } else if row & 0b10 == 0 {
-            bus.set_byte(0xFF00, (self.row_2 & 0xF) | 0b11110000);
+fn run_headless(gameboy: &mut GameBoy, options: &cli::Options) -> Result<(), String> {
+    let frames = options.frames.unwrap_or(0);
+    let mut samples: Vec<f32> = Vec::new();
+    for _ in 0..frames {
+        gameboy.run_frame();
+        report_events(gameboy);
+        gameboy.autosave()?;
+        if options.wav_path.is_some() {
+            samples.append(&mut gameboy.take_audio_samples());
+        } else {
+            gameboy.take_audio_samples(); // discard audio
         }
     }
+    if let Some(path) = &options.wav_path {
+        apu::write_wav(path, &samples).map_err(|err| format!("Could not write {} : {}", path, err))?;
+    }
+    Ok(())
 }
 
-fn main() {
-    let x_size: u32 = 160;
-    let y_size: u32 = 144;
-    let scale: f32 = 2.0;
-
-    let mut bus: bus::Bus = bus::Bus::new_bus(&String::from("roms/Tetris.GB"));
-    //let mut bus: bus::Bus = bus::Bus::new_bus(&String::from("roms/11-op a,(hl).gb"));
-    let mut cpu = cpu::CPU::new_cpu();
-    let mut gpu = gpu::GPU::new_gpu();
-    let mut keys = Keys::new_keys();
+fn run_sdl(gameboy: &mut GameBoy, options: &cli::Options, tcp_link: Option<link::TcpLink>) -> Result<(), String> {
+    let x_size: u32 = gpu::GPU::SCREEN_WIDTH as u32;
+    let y_size: u32 = gpu::GPU::SCREEN_HEIGHT as u32;
+    let scale: f32 = options.scale;
 
     let mut debugger = debugger::Debugger::new_debugger();
-    //debugger.set_paused(true);
-    let debug = false;
+    for breakpoint in &options.breakpoints {
+        debugger.add_breakpoint(*breakpoint);
+    }
+    let debug = options.debug;
 
-    let sdl_context = sdl2::init().unwrap();
-    let video_subsystem = sdl_context.video().unwrap();
-    let mut event_pump = sdl_context.event_pump().expect("Failed to generate event pump !");
+    let bindings = match &options.bindings {
+        Some(path) => bindings::Bindings::from_file(path)?,
+        None if Path::new(bindings::Bindings::DEFAULT_PATH).exists() => bindings::Bindings::from_file(bindings::Bindings::DEFAULT_PATH)?,
+        None => bindings::Bindings::new_default_bindings(),
+    };
 
-    let window = video_subsystem.window("GB Emulator", (scale as u32) * x_size, (scale as u32) * y_size)
+    let sdl_context = sdl2::init()?;
+    let video_subsystem = sdl_context.video()?;
+    let controller_subsystem = sdl_context.game_controller()?;
+    let mut controllers = Vec::new(); // gamepads are only reported while opened
+    let event_pump = Rc::new(RefCell::new(sdl_context.event_pump()?));
+    let waiting_events = Rc::new(RefCell::new(Vec::new())); // received while the link waited for the other side
+
+    if let Some(mut tcp_link) = tcp_link {
+        // keep the window responsive while the other side is paused, the events are handled after the wait
+        let event_pump = Rc::clone(&event_pump);
+        let waiting_events = Rc::clone(&waiting_events);
+        tcp_link.set_wait_callback(Box::new(move || {
+            let mut keep_waiting = true;
+            for event in event_pump.borrow_mut().poll_iter() {
+                if let Event::Quit { .. } | Event::KeyDown { keycode: Some(Keycode::Escape), .. } = event {
+                    keep_waiting = false;
+                }
+                waiting_events.borrow_mut().push(event);
+            }
+            keep_waiting
+        }));
+        gameboy.connect_link(Box::new(tcp_link));
+    }
+
+    let title = if gameboy.header().title.is_empty() {
+        String::from("GB Emulator")
+    } else {
+        format!("GB Emulator - {}", gameboy.header().title)
+    };
+    let window = video_subsystem.window(&title, (scale * x_size as f32) as u32, (scale * y_size as f32) as u32)
         .position_centered()
         .build()
-        .unwrap();
+        .map_err(|err| err.to_string())?;
 
-    let mut canvas = window.into_canvas().build().unwrap();
+    let mut canvas = window.into_canvas().build().map_err(|err| err.to_string())?;
     canvas.set_draw_color(sdl2::pixels::Color::RGB(255, 255, 255));
     canvas.clear();
     canvas.present();
-    canvas.set_scale(scale, scale).unwrap();
+    let texture_creator = canvas.texture_creator();
+    let mut texture = texture_creator.create_texture_streaming(sdl2::pixels::PixelFormatEnum::RGB24, x_size, y_size)
+        .map_err(|err| err.to_string())?;
+
+    let audio_queue: Option<sdl2::audio::AudioQueue<f32>> = if options.mute {
+        None
+    } else {
+        let audio_spec = sdl2::audio::AudioSpecDesired {
+            freq: Some(apu::APU::SAMPLE_RATE as i32),
+            channels: Some(2),
+            samples: Some(1024),
+        };
+        let queue = sdl_context.audio()?.open_queue(None, &audio_spec)?;
+        queue.resume();
+        Some(queue)
+    };
+    let max_queued_bytes = apu::APU::SAMPLE_RATE * 2 * 4 / 10; // keep at most 100ms of stereo f32 samples queued
 
+    let mut frames = 0;
+    let mut next_frame = Instant::now(); // when the next frame is due, to run at the speed of the hardware
     'main_loop: loop {
-        for event in event_pump.poll_iter() {
+        let mut events: Vec<Event> = waiting_events.borrow_mut().drain(..).collect();
+        events.extend(event_pump.borrow_mut().poll_iter());
+        for event in events {
             match event {
                 Event::Quit { .. } => break 'main_loop,
                 Event::KeyDown { keycode: Some(Keycode::Escape), .. } => break 'main_loop,
                 Event::KeyDown { keycode: Some(Keycode::F1), .. } => debugger.set_paused(true),
-                _ => keys.update_keys(event),
+                Event::KeyDown { keycode: Some(key), keymod, repeat: false, .. } if keycode_to_state_slot(key).is_some() => {
+                    let slot = keycode_to_state_slot(key).unwrap();
+                    let result = if keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD) {
+                        save_state_slot(gameboy, options, slot)
+                    } else {
+                        load_state_slot(gameboy, options, slot)
+                    };
+                    if let Err(err) = result {
+                        eprintln!("Warning : {}", err);
+                    }
+                },
+                Event::ControllerDeviceAdded { which, .. } => {
+                    match controller_subsystem.open(which) {
+                        Ok(controller) => controllers.push(controller),
+                        Err(err) => eprintln!("Warning : could not open gamepad {} : {}", which, err),
+                    }
+                },
+                _ => update_keys(gameboy, &bindings, event),
             };
         }
-        if debug == true {
-            debugger.tick(&mut cpu, &mut bus, &mut gpu, &mut keys, &mut canvas);
+        let frame_completed = if debug {
+            debugger.tick(gameboy)
         } else {
-            keys.update_register(&mut bus);
-            gpu.tick(&mut bus, &mut canvas);
-            cpu.tick(&mut bus);
+            gameboy.run_frame();
+            report_events(gameboy);
+            true
+        };
+        if frame_completed {
+            render_canvas(gameboy, &mut canvas, &mut texture)?;
+            if let Err(err) = gameboy.autosave() {
+                eprintln!("Warning : {}", err);
+            }
+            frames += 1;
+            if options.frames == Some(frames) {
+                break 'main_loop;
+            }
+
+            next_frame += FRAME_DURATION;
+            let now = Instant::now();
+            if next_frame > now {
+                thread::sleep(next_frame - now);
+            } else if now - next_frame > MAX_FRAME_DELAY {
+                next_frame = now; // after a pause in the debugger or a slow frame, do not run fast to catch up
+            }
+        }
+
+        if gameboy.audio_sample_count() >= 1024 {
+            let samples = gameboy.take_audio_samples();
+            if let Some(queue) = &audio_queue {
+                if queue.size() < max_queued_bytes { // drop samples rather than build up latency
+                    queue.queue(&samples);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    let options = match cli::parse_args(env::args().skip(1).collect()) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            cli::print_usage();
+            return;
+        },
+        Err(err) => {
+            eprintln!("Error : {}", err);
+            cli::print_usage();
+            process::exit(2);
+        },
+    };
+    let mut gameboy = match GameBoy::new_gameboy(&options.rom_path) {
+        Ok(gameboy) => gameboy,
+        Err(err) => {
+            eprintln!("Error : {}", err);
+            process::exit(1);
+        },
+    };
+
+    if let Some(path) = &options.boot_rom {
+        let result = fs::read(path)
+            .map_err(|err| format!("Could not read boot ROM {} : {}", path, err))
+            .and_then(|boot_rom| gameboy.load_boot_rom(boot_rom));
+        if let Err(err) = result {
+            eprintln!("Error : {}", err);
+            process::exit(1);
+        }
+    }
+
+    match options.link.as_deref() {
+        Some("stdout") => gameboy.connect_link(Box::new(serial::StdoutLogger)),
+        Some("loopback") => gameboy.connect_link(Box::new(serial::Loopback)),
+        _ => (),
+    }
+    let tcp_link = if let Some(port) = options.link_host {
+        println!("Waiting for the other emulator to join on port {}", port);
+        Some(link::TcpLink::host(port))
+    } else {
+        options.link_join.as_ref().map(|address| link::TcpLink::join(address))
+    };
+    let tcp_link = match tcp_link {
+        Some(Ok(tcp_link)) => Some(tcp_link),
+        Some(Err(err)) => {
+            eprintln!("Error : {}", err);
+            process::exit(1);
+        },
+        None => None,
+    };
+
+    for warning in gameboy.header().warnings() {
+        eprintln!("Warning : {}", warning);
+    }
+    let save_path = save::save_path(&options.rom_path, options.save_dir.as_deref());
+    if let Err(err) = gameboy.load_battery_save(save_path) {
+        eprintln!("Error : {}", err);
+        process::exit(1);
+    }
+
+    let result = if options.headless {
+        if let Some(tcp_link) = tcp_link {
+            gameboy.connect_link(Box::new(tcp_link));
         }
+        run_headless(&mut gameboy, &options)
+    } else {
+        run_sdl(&mut gameboy, &options, tcp_link)
+    };
+    // save on exit, even if the run ended with an error
+    let result = result.and(gameboy.flush_battery_save());
+    if let Err(err) = result {
+        eprintln!("Error : {}", err);
+        process::exit(1);
     }
 }
@@ -0,0 +1,215 @@
+use crate::state;
+
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+pub struct RTC {
+    seconds: u8,
+    minutes: u8,
+    hours: u8,
+    days: u16, // 9 bits day counter
+    halted: bool,
+    day_carry: bool,
+    latched: [u8; 5], // values of the registers at the last latch, as read by the CPU
+    latch_prepared: bool, // 0x00 was written to the latch register, waiting for 0x01
+    deterministic: bool, // advance with emulated clock cycles instead of host time
+    clocks: u32, // emulated clock cycles since the last second
+    last_sync: u64, // host time in seconds of the last update
+}
+
+impl RTC {
+    const CLOCK_RATE: u32 = 4194304;
+    pub const FOOTER_SIZE: usize = 48;
+
+    // values of the RAM bank register selecting each RTC register
+    const SECONDS: u8 = 0x08;
+    const MINUTES: u8 = 0x09;
+    const HOURS: u8 = 0x0A;
+    const DAYS_LOW: u8 = 0x0B;
+    const DAYS_HIGH: u8 = 0x0C;
+
+    pub fn new_rtc() -> RTC {
+        RTC {
+            seconds: 0,
+            minutes: 0,
+            hours: 0,
+            days: 0,
+            halted: false,
+            day_carry: false,
+            latched: [0; 5],
+            latch_prepared: false,
+            deterministic: false,
+            clocks: 0,
+            last_sync: RTC::host_time(),
+        }
+    }
+
+    fn host_time() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+    }
+
+    pub fn set_deterministic(&mut self, deterministic: bool) {
+        self.deterministic = deterministic;
+        self.clocks = 0;
+        self.last_sync = RTC::host_time();
+    }
+
+    pub fn is_register(register: u8) -> bool {
+        (RTC::SECONDS..=RTC::DAYS_HIGH).contains(&register)
+    }
+
+    pub fn tick(&mut self) {
+        // emulated clock cycle, only used in deterministic mode
+        if !self.deterministic {
+            return;
+        }
+        self.clocks += 1;
+        if self.clocks == RTC::CLOCK_RATE {
+            self.clocks = 0;
+            self.advance(1);
+        }
+    }
+
+    fn sync(&mut self) {
+        // catch up with host time
+        if self.deterministic {
+            return;
+        }
+        let now = RTC::host_time();
+        if now > self.last_sync {
+            self.advance(now - self.last_sync);
+        }
+        self.last_sync = now;
+    }
+
+    fn advance(&mut self, seconds: u64) {
+        if self.halted || seconds == 0 {
+            return;
+        }
+        let total = self.seconds as u64
+            + 60 * (self.minutes as u64)
+            + 3600 * (self.hours as u64)
+            + 86400 * (self.days as u64)
+            + seconds;
+        self.seconds = (total % 60) as u8;
+        self.minutes = ((total / 60) % 60) as u8;
+        self.hours = ((total / 3600) % 24) as u8;
+        let days = total / 86400;
+        if days > 511 {
+            self.day_carry = true; // stays set until cleared by the game
+        }
+        self.days = (days % 512) as u16;
+    }
+
+    fn current_registers(&self) -> [u8; 5] {
+        [
+            self.seconds,
+            self.minutes,
+            self.hours,
+            (self.days & 0xFF) as u8,
+            ((self.days >> 8) as u8 & 1) | ((self.halted as u8) << 6) | ((self.day_carry as u8) << 7),
+        ]
+    }
+
+    pub fn write_latch(&mut self, data: u8) {
+        // writing 0x00 then 0x01 copies the clock to the latched registers
+        if self.latch_prepared && data == 1 {
+            self.sync();
+            self.latched = self.current_registers();
+        }
+        self.latch_prepared = data == 0;
+    }
+
+    pub fn fetch_register(&self, register: u8) -> u8 {
+        let value = self.latched[(register - RTC::SECONDS) as usize];
+        match register {
+            RTC::SECONDS | RTC::MINUTES => value & 0x3F,
+            RTC::HOURS => value & 0x1F,
+            RTC::DAYS_HIGH => value & 0xC1,
+            _ => value,
+        }
+    }
+
+    pub fn set_register(&mut self, register: u8, data: u8) {
+        self.sync();
+        match register {
+            RTC::SECONDS => {
+                self.seconds = data & 0x3F;
+                self.clocks = 0; // writing seconds resets the sub second counter
+            },
+            RTC::MINUTES => self.minutes = data & 0x3F,
+            RTC::HOURS => self.hours = data & 0x1F,
+            RTC::DAYS_LOW => self.days = (self.days & 0x100) | data as u16,
+            _ => {
+                self.days = (self.days & 0xFF) | (((data & 1) as u16) << 8);
+                self.halted = data & 0b1000000 != 0;
+                self.day_carry = data & 0b10000000 != 0;
+            },
+        }
+        self.latched[(register - RTC::SECONDS) as usize] = data;
+    }
+
+    pub fn save_state(&self, writer: &mut state::StateWriter) {
+        for register in self.current_registers().iter().chain(self.latched.iter()) {
+            writer.write_u8(*register);
+        }
+        writer.write_bool(self.latch_prepared);
+        writer.write_u32(self.clocks);
+        writer.write_u64(self.last_sync);
+    }
+
+    pub fn load_state(&mut self, reader: &mut state::StateReader) -> Result<(), String> {
+        let mut registers = [0u8; 5];
+        for register in registers.iter_mut() {
+            *register = reader.read_u8()?;
+        }
+        self.seconds = registers[0];
+        self.minutes = registers[1];
+        self.hours = registers[2];
+        self.days = (registers[3] as u16) | (((registers[4] & 1) as u16) << 8);
+        self.halted = registers[4] & 0b1000000 != 0;
+        self.day_carry = registers[4] & 0b10000000 != 0;
+        for register in self.latched.iter_mut() {
+            *register = reader.read_u8()?;
+        }
+        self.latch_prepared = reader.read_bool()?;
+        self.clocks = reader.read_u32()?;
+        self.last_sync = reader.read_u64()?;
+        self.sync(); // a state loaded later in host time should not rewind the clock
+        Ok(())
+    }
+
+    pub fn footer(&mut self) -> Vec<u8> {
+        // common save format : current and latched registers as 32 bits values, then a 64 bits timestamp
+        self.sync();
+        let mut footer = Vec::with_capacity(RTC::FOOTER_SIZE);
+        for register in self.current_registers().iter().chain(self.latched.iter()) {
+            footer.extend_from_slice(&(*register as u32).to_le_bytes());
+        }
+        footer.extend_from_slice(&RTC::host_time().to_le_bytes());
+        footer
+    }
+
+    pub fn load_footer(&mut self, footer: &[u8]) {
+        if footer.len() < RTC::FOOTER_SIZE {
+            return;
+        }
+        let mut values = [0u8; 10];
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = footer[i * 4];
+        }
+        self.seconds = values[0] & 0x3F;
+        self.minutes = values[1] & 0x3F;
+        self.hours = values[2] & 0x1F;
+        self.days = (values[3] as u16) | (((values[4] & 1) as u16) << 8);
+        self.halted = values[4] & 0b1000000 != 0;
+        self.day_carry = values[4] & 0b10000000 != 0;
+        self.latched.copy_from_slice(&values[5..10]);
+
+        let mut timestamp = [0u8; 8];
+        timestamp.copy_from_slice(&footer[40..48]);
+        self.last_sync = u64::from_le_bytes(timestamp);
+        self.clocks = 0;
+        self.sync(); // account for the time spent while the emulator was closed
+    }
+}
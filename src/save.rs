@@ -0,0 +1,43 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+
+pub fn save_path(rom_path: &str, save_dir: Option<&str>) -> PathBuf {
+    // the save has the name of the ROM with a .sav extension, next to the ROM unless a directory is given
+    let rom_path = Path::new(rom_path);
+    let file_name = rom_path.with_extension("sav");
+    match save_dir {
+        Some(dir) => Path::new(dir).join(file_name.file_name().unwrap_or_default()),
+        None => file_name,
+    }
+}
+
+pub fn state_path(rom_path: &str, save_dir: Option<&str>, slot: u8) -> PathBuf {
+    // save states are stored like battery saves, with a .ss<slot> extension
+    save_path(rom_path, save_dir).with_extension(format!("ss{}", slot))
+}
+
+pub fn read_save(path: &Path) -> Result<Option<Vec<u8>>, String> {
+    // returns None if there is no save yet
+    match fs::read(path) {
+        Ok(data) => Ok(Some(data)),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(format!("Could not read save file {} : {}", path.display(), err)),
+    }
+}
+
+pub fn write_save(path: &Path, data: &[u8]) -> Result<(), String> {
+    // write to a temporary file first so a crash can't leave a truncated save behind
+    let mut temporary_path = path.as_os_str().to_owned();
+    temporary_path.push(".tmp");
+    let temporary_path = PathBuf::from(temporary_path);
+    if let Some(dir) = path.parent() {
+        if !dir.as_os_str().is_empty() {
+            fs::create_dir_all(dir).map_err(|err| format!("Could not create save directory {} : {}", dir.display(), err))?;
+        }
+    }
+    fs::write(&temporary_path, data)
+        .and_then(|_| fs::rename(&temporary_path, path))
+        .map_err(|err| format!("Could not write save file {} : {}", path.display(), err))
+}
@@ -0,0 +1,144 @@
+use crate::state;
+
+use std::io::Write;
+
+pub trait LinkEndpoint {
+    // what is plugged on the other end of the link cable
+
+    // this side starts a transfer with its internal clock : send a byte and return the byte received
+    fn exchange(&mut self, data: u8) -> u8;
+
+    // called on every shift clock while this side does not drive a transfer : if the other side clocked one, send data and return the byte received
+    fn poll_external(&mut self, _data: u8) -> Option<u8> {
+        None
+    }
+}
+
+pub struct Disconnected;
+
+impl LinkEndpoint for Disconnected {
+    fn exchange(&mut self, _data: u8) -> u8 {
+        0xFF // the input line is pulled up
+    }
+}
+
+pub struct Loopback;
+
+impl LinkEndpoint for Loopback {
+    fn exchange(&mut self, data: u8) -> u8 {
+        data // the output line is wired to the input line
+    }
+}
+
+pub struct StdoutLogger;
+
+impl LinkEndpoint for StdoutLogger {
+    fn exchange(&mut self, data: u8) -> u8 {
+        // test ROMs print their results this way
+        print!("{}", data as char);
+        std::io::stdout().flush().ok();
+        0xFF
+    }
+}
+
+pub struct Serial {
+    data: u8, // SB
+    control: u8, // SC, only bits 7 and 0 exist
+    received: u8, // byte being shifted in, most significant bit first
+    bits_left: u8, // 0 when no bit is being shifted
+    endpoint: Box<dyn LinkEndpoint>,
+}
+
+impl Serial {
+    pub const TRANSFER_DATA: u16 = 0xFF01;
+    pub const TRANSFER_CONTROL: u16 = 0xFF02;
+
+    const TRANSFER_START: u8 = 0b10000000;
+    const INTERNAL_CLOCK: u8 = 0b1;
+    const SHIFT_CLOCK_MASK: u16 = 0x1FF; // the internal clock is the falling edge of bit 8 of the divider, 8192 Hz
+
+    pub fn new_serial() -> Serial {
+        Serial { data: 0, control: 0, received: 0, bits_left: 0, endpoint: Box::new(Disconnected) }
+    }
+
+    pub fn connect(&mut self, endpoint: Box<dyn LinkEndpoint>) {
+        self.endpoint = endpoint;
+    }
+
+    pub fn reset(&mut self) {
+        // back to the power on state, the link cable stays plugged in
+        self.data = 0;
+        self.control = 0;
+        self.received = 0;
+        self.bits_left = 0;
+    }
+
+    pub fn save_state(&self, writer: &mut state::StateWriter) {
+        writer.write_u8(self.data);
+        writer.write_u8(self.control);
+        writer.write_u8(self.received);
+        writer.write_u8(self.bits_left);
+    }
+
+    pub fn load_state(&mut self, reader: &mut state::StateReader) -> Result<(), String> {
+        self.data = reader.read_u8()?;
+        self.control = reader.read_u8()?;
+        self.received = reader.read_u8()?;
+        self.bits_left = reader.read_u8()?;
+        Ok(())
+    }
+
+    pub fn tick(&mut self, divider: u16) -> bool {
+        // advance the transfer by one clock cycle ; returns true if the serial interrupt must be requested
+        if divider & Serial::SHIFT_CLOCK_MASK != 0 {
+            return false;
+        }
+        if self.control & Serial::INTERNAL_CLOCK == 0 || self.bits_left == 0 {
+            // the other side may clock a transfer, our byte is shifted out even if no transfer is enabled here
+            if let Some(received) = self.endpoint.poll_external(self.data) {
+                if self.control == Serial::TRANSFER_START && self.bits_left == 0 {
+                    self.received = received;
+                    self.bits_left = 8;
+                }
+            }
+        }
+        if self.control & Serial::TRANSFER_START == 0 || self.bits_left == 0 {
+            return false;
+        }
+        self.data = (self.data << 1) | (self.received >> 7);
+        self.received <<= 1;
+        self.bits_left -= 1;
+        if self.bits_left == 0 {
+            self.control &= !Serial::TRANSFER_START;
+            return true;
+        }
+        false
+    }
+
+    pub fn fetch_byte(&self, address: u16) -> u8 {
+        match address {
+            Serial::TRANSFER_DATA => self.data,
+            Serial::TRANSFER_CONTROL => self.control | 0b01111110, // unused bits read as 1
+            _ => panic!("Address {:#06x} is not a serial register !", address),
+        }
+    }
+
+    pub fn set_byte(&mut self, address: u16, data: u8) {
+        match address {
+            Serial::TRANSFER_DATA => self.data = data,
+            Serial::TRANSFER_CONTROL => {
+                let previous_control = self.control;
+                self.control = data & (Serial::TRANSFER_START | Serial::INTERNAL_CLOCK);
+                if self.control != previous_control {
+                    self.bits_left = 0; // rewriting the same value does not restart the transfer in progress
+                }
+                if self.control == Serial::TRANSFER_START | Serial::INTERNAL_CLOCK && self.bits_left == 0 {
+                    // the whole byte is exchanged now and shifted in at the clock rate
+                    self.received = self.endpoint.exchange(self.data);
+                    self.bits_left = 8;
+                }
+            },
+            _ => panic!("Address {:#06x} is not a serial register !", address),
+        }
+    }
+}
@@ -0,0 +1,115 @@
+pub struct StateWriter {
+    data: Vec<u8>,
+}
+
+impl StateWriter {
+    pub fn new_writer() -> StateWriter {
+        StateWriter { data: Vec::new() }
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.data.push(value);
+    }
+
+    pub fn write_bool(&mut self, value: bool) {
+        self.data.push(value as u8);
+    }
+
+    pub fn write_u16(&mut self, value: u16) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_u64(&mut self, value: u64) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        // length prefixed so the reader can check it against the expected size
+        self.write_u32(bytes.len() as u32);
+        self.data.extend_from_slice(bytes);
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+pub struct StateReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl StateReader<'_> {
+    pub fn new_reader(data: &[u8]) -> StateReader<'_> {
+        StateReader { data, position: 0 }
+    }
+
+    fn take(&mut self, length: usize) -> Result<&[u8], String> {
+        if self.position + length > self.data.len() {
+            return Err(String::from("Save state is truncated"));
+        }
+        let bytes = &self.data[self.position..self.position + length];
+        self.position += length;
+        Ok(bytes)
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool, String> {
+        Ok(self.read_u8()? != 0)
+    }
+
+    pub fn read_u8_at_most(&mut self, max: u8, name: &str) -> Result<u8, String> {
+        // for values the emulator can not run with, a damaged state is rejected instead of crashing later
+        let value = self.read_u8()?;
+        if value > max {
+            return Err(format!("Save state has an invalid {} ({})", name, value));
+        }
+        Ok(value)
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16, String> {
+        let mut bytes = [0u8; 2];
+        bytes.copy_from_slice(self.take(2)?);
+        Ok(u16::from_le_bytes(bytes))
+    }
+
+    pub fn read_u16_at_most(&mut self, max: u16, name: &str) -> Result<u16, String> {
+        let value = self.read_u16()?;
+        if value > max {
+            return Err(format!("Save state has an invalid {} ({})", name, value));
+        }
+        Ok(value)
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, String> {
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(self.take(4)?);
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, String> {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    pub fn read_bytes_into(&mut self, destination: &mut [u8]) -> Result<(), String> {
+        let length = self.read_u32()? as usize;
+        if length != destination.len() {
+            return Err(format!("Save state block has {} bytes, expected {}", length, destination.len()));
+        }
+        destination.copy_from_slice(self.take(length)?);
+        Ok(())
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.position == self.data.len()
+    }
+}
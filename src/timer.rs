@@ -0,0 +1,113 @@
+use crate::state;
+
+pub struct Timer {
+    counter: u16, // internal counter incremented every clock cycle ; DIV is its upper 8 bits
+    tima: u8,
+    tma: u8,
+    tac: u8,
+    reload_delay: u8, // clock cycles left before TIMA is reloaded from TMA after an overflow
+}
+
+impl Timer {
+    const DIVIDER_REGISTER: u16 = 0xFF04;
+    const TIMER_COUNTER: u16 = 0xFF05;
+    const TIMER_MODULO: u16 = 0xFF06;
+    const TIMER_CONTROL: u16 = 0xFF07;
+
+    const RELOAD_DELAY_CLOCKS: u8 = 4;
+    const POST_BOOT_COUNTER: u16 = 0xABCC; // DIV reads 0xAB when the cartridge starts
+
+    pub fn new_timer() -> Timer {
+        Timer { counter: 0, tima: 0, tma: 0, tac: 0, reload_delay: 0 }
+    }
+
+    pub fn new_post_boot_timer() -> Timer {
+        Timer { counter: Timer::POST_BOOT_COUNTER, ..Timer::new_timer() }
+    }
+
+    pub fn save_state(&self, writer: &mut state::StateWriter) {
+        writer.write_u16(self.counter);
+        writer.write_u8(self.tima);
+        writer.write_u8(self.tma);
+        writer.write_u8(self.tac);
+        writer.write_u8(self.reload_delay);
+    }
+
+    pub fn load_state(&mut self, reader: &mut state::StateReader) -> Result<(), String> {
+        self.counter = reader.read_u16()?;
+        self.tima = reader.read_u8()?;
+        self.tma = reader.read_u8()?;
+        self.tac = reader.read_u8()?;
+        self.reload_delay = reader.read_u8()?;
+        Ok(())
+    }
+
+    pub fn internal_counter(&self) -> u16 {
+        self.counter
+    }
+
+    fn timer_input(&self) -> bool {
+        // TIMA is clocked by the falling edge of one bit of the internal counter, ANDed with the enable bit
+        let bit = match self.tac & 0b11 {
+            0b00 => 9, // 4096 Hz
+            0b01 => 3, // 262144 Hz
+            0b10 => 5, // 65536 Hz
+            _ => 7, // 16384 Hz
+        };
+        self.tac & 0b100 != 0 && (self.counter >> bit) & 1 == 1
+    }
+
+    fn increment_on_falling_edge(&mut self, previous_input: bool) {
+        if previous_input && !self.timer_input() {
+            let (result, overflow) = self.tima.overflowing_add(1);
+            self.tima = result;
+            if overflow {
+                // TIMA stays at 0 for a full M-cycle before being reloaded
+                self.reload_delay = Timer::RELOAD_DELAY_CLOCKS;
+            }
+        }
+    }
+
+    pub fn tick(&mut self) -> bool {
+        // advance the timer by one clock cycle ; returns true if the timer interrupt must be requested
+        let mut interrupt = false;
+        if self.reload_delay > 0 {
+            self.reload_delay -= 1;
+            if self.reload_delay == 0 {
+                self.tima = self.tma;
+                interrupt = true;
+            }
+        }
+
+        let previous_input = self.timer_input();
+        self.counter = self.counter.wrapping_add(1);
+        self.increment_on_falling_edge(previous_input);
+        interrupt
+    }
+
+    pub fn fetch_byte(&self, address: u16) -> u8 {
+        match address {
+            Timer::DIVIDER_REGISTER => (self.counter >> 8) as u8,
+            Timer::TIMER_COUNTER => self.tima,
+            Timer::TIMER_MODULO => self.tma,
+            Timer::TIMER_CONTROL => self.tac | 0b11111000, // unused bits read as 1
+            _ => panic!("Address {:#06x} is not a timer register !", address),
+        }
+    }
+
+    pub fn set_byte(&mut self, address: u16, data: u8) {
+        let previous_input = self.timer_input();
+        match address {
+            Timer::DIVIDER_REGISTER => self.counter = 0, // any write resets the whole internal counter
+            Timer::TIMER_COUNTER => {
+                self.tima = data;
+                self.reload_delay = 0; // writing TIMA during the overflow cycle cancels the reload
+            },
+            Timer::TIMER_MODULO => self.tma = data,
+            Timer::TIMER_CONTROL => self.tac = data & 0b111,
+            _ => panic!("Address {:#06x} is not a timer register !", address),
+        }
+        // resetting DIV or changing TAC can produce a falling edge and increment TIMA
+        self.increment_on_falling_edge(previous_input);
+    }
+}
@@ -0,0 +1,86 @@
+// blargg's test ROMs, which print their result on the serial port, or only on the screen for halt_bug
+
+mod common;
+
+use common::assert_blargg_passes;
+use common::assert_blargg_screen_passes;
+
+#[test]
+fn cpu_instrs_01_special() {
+    assert_blargg_passes("blargg/cpu_instrs/individual/01-special.gb");
+}
+
+#[test]
+fn cpu_instrs_02_interrupts() {
+    assert_blargg_passes("blargg/cpu_instrs/individual/02-interrupts.gb");
+}
+
+#[test]
+fn cpu_instrs_03_op_sp_hl() {
+    assert_blargg_passes("blargg/cpu_instrs/individual/03-op sp,hl.gb");
+}
+
+#[test]
+fn cpu_instrs_04_op_r_imm() {
+    assert_blargg_passes("blargg/cpu_instrs/individual/04-op r,imm.gb");
+}
+
+#[test]
+fn cpu_instrs_05_op_rp() {
+    assert_blargg_passes("blargg/cpu_instrs/individual/05-op rp.gb");
+}
+
+#[test]
+fn cpu_instrs_06_ld_r_r() {
+    assert_blargg_passes("blargg/cpu_instrs/individual/06-ld r,r.gb");
+}
+
+#[test]
+fn cpu_instrs_07_jr_jp_call_ret_rst() {
+    assert_blargg_passes("blargg/cpu_instrs/individual/07-jr,jp,call,ret,rst.gb");
+}
+
+#[test]
+fn cpu_instrs_08_misc_instrs() {
+    assert_blargg_passes("blargg/cpu_instrs/individual/08-misc instrs.gb");
+}
+
+#[test]
+fn cpu_instrs_09_op_r_r() {
+    assert_blargg_passes("blargg/cpu_instrs/individual/09-op r,r.gb");
+}
+
+#[test]
+fn cpu_instrs_10_bit_ops() {
+    assert_blargg_passes("blargg/cpu_instrs/individual/10-bit ops.gb");
+}
+
+#[test]
+fn cpu_instrs_11_op_a_hl() {
+    assert_blargg_passes("blargg/cpu_instrs/individual/11-op a,(hl).gb");
+}
+
+#[test]
+fn halt_bug() {
+    assert_blargg_screen_passes("blargg/halt_bug.gb");
+}
+
+#[test]
+fn instr_timing() {
+    assert_blargg_passes("blargg/instr_timing/instr_timing.gb");
+}
+
+#[test]
+fn mem_timing_01_read_timing() {
+    assert_blargg_passes("blargg/mem_timing/individual/01-read_timing.gb");
+}
+
+#[test]
+fn mem_timing_02_write_timing() {
+    assert_blargg_passes("blargg/mem_timing/individual/02-write_timing.gb");
+}
+
+#[test]
+fn mem_timing_03_modify_timing() {
+    assert_blargg_passes("blargg/mem_timing/individual/03-modify_timing.gb");
+}
@@ -0,0 +1,132 @@
+// Helpers to run the test ROMs of roms/gameboy-test-roms-v3.0.zip headless
+// each test binary only uses the helpers for its own kind of ROM
+#![allow(dead_code)]
+
+use gbemulator::serial::LinkEndpoint;
+use gbemulator::GameBoy;
+
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::Read;
+use std::rc::Rc;
+
+const TEST_ROMS_ZIP: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/roms/gameboy-test-roms-v3.0.zip");
+const CLOCKS_PER_SECOND: u64 = 4_194_304;
+
+// registers set by mooneye tests before executing LD B,B
+const MOONEYE_PASS: [u8; 6] = [3, 5, 8, 13, 21, 34];
+const MOONEYE_FAIL: [u8; 6] = [0x42; 6];
+const LD_B_B: u8 = 0x40;
+
+// blargg tests without serial output print on the background map, with a font whose tile numbers are ASCII codes
+const BG_MAP: u16 = 0x9800;
+const BG_MAP_SIZE: u16 = 32;
+
+pub fn load_rom(name: &str) -> Vec<u8> {
+    // extract a ROM from the test ROM archive
+    let file = File::open(TEST_ROMS_ZIP).unwrap_or_else(|err| panic!("Could not open {} : {}", TEST_ROMS_ZIP, err));
+    let mut archive = zip::ZipArchive::new(file).expect("Test ROM archive is not a valid zip file");
+    let mut entry = archive.by_name(name).unwrap_or_else(|_| panic!("{} is not in the test ROM archive", name));
+    let mut rom = Vec::new();
+    entry.read_to_end(&mut rom).expect("Could not extract the test ROM");
+    rom
+}
+
+struct SerialCapture {
+    output: Rc<RefCell<String>>,
+}
+
+impl LinkEndpoint for SerialCapture {
+    fn exchange(&mut self, data: u8) -> u8 {
+        self.output.borrow_mut().push(data as char);
+        0xFF
+    }
+}
+
+pub fn run_blargg(rom: Vec<u8>, seconds: u64) -> Result<String, String> {
+    // run until the test prints Passed or Failed on the serial port, returns everything it printed
+    let mut gameboy = GameBoy::from_rom(rom)?;
+    let output = Rc::new(RefCell::new(String::new()));
+    gameboy.connect_link(Box::new(SerialCapture { output: output.clone() }));
+    while gameboy.bus.clock_cycles() < seconds * CLOCKS_PER_SECOND {
+        gameboy.step();
+        let output = output.borrow();
+        if output.contains("Passed") {
+            return Ok(output.clone());
+        }
+        if output.contains("Failed") {
+            return Err(output.clone());
+        }
+    }
+    let output = output.borrow();
+    Err(format!("{}\nTimed out after {} seconds", output, seconds))
+}
+
+pub fn screen_text(gameboy: &GameBoy) -> String {
+    // characters of the whole background map, one line per row of tiles
+    let mut text = String::new();
+    for row in 0..BG_MAP_SIZE {
+        for column in 0..BG_MAP_SIZE {
+            let tile = gameboy.bus.fetch_byte(BG_MAP + row * BG_MAP_SIZE + column);
+            text.push(if tile.is_ascii_graphic() { tile as char } else { ' ' });
+        }
+        text.push('\n');
+    }
+    text
+}
+
+pub fn run_blargg_screen(rom: Vec<u8>, seconds: u64) -> Result<String, String> {
+    // run until the test shows Passed or Failed on the screen, returns the screen
+    let mut gameboy = GameBoy::from_rom(rom)?;
+    while gameboy.bus.clock_cycles() < seconds * CLOCKS_PER_SECOND {
+        gameboy.run_frame();
+        let text = screen_text(&gameboy);
+        if text.contains("Passed") {
+            return Ok(text);
+        }
+        if text.contains("Failed") {
+            return Err(text);
+        }
+    }
+    Err(format!("{}\nTimed out after {} seconds", screen_text(&gameboy), seconds))
+}
+
+pub fn run_mooneye(rom: Vec<u8>, seconds: u64) -> Result<(), String> {
+    // run until the test signals its result with LD B,B and the registers
+    let mut gameboy = GameBoy::from_rom(rom)?;
+    while gameboy.bus.clock_cycles() < seconds * CLOCKS_PER_SECOND {
+        gameboy.step();
+        if let Some(event) = gameboy.take_event() {
+            return Err(event.describe());
+        }
+        if gameboy.bus.fetch_byte(gameboy.cpu.pc) == LD_B_B {
+            let cpu = &gameboy.cpu;
+            let registers = [cpu.bc.high, cpu.bc.low, cpu.de.high, cpu.de.low, cpu.hl.high, cpu.hl.low];
+            if registers == MOONEYE_PASS {
+                return Ok(());
+            }
+            if registers == MOONEYE_FAIL {
+                return Err(String::from("Failed"));
+            }
+        }
+    }
+    Err(format!("Timed out after {} seconds", seconds))
+}
+
+pub fn assert_blargg_passes(name: &str) {
+    if let Err(output) = run_blargg(load_rom(name), 120) {
+        panic!("{} :\n{}", name, output);
+    }
+}
+
+pub fn assert_blargg_screen_passes(name: &str) {
+    if let Err(output) = run_blargg_screen(load_rom(name), 120) {
+        panic!("{} :\n{}", name, output);
+    }
+}
+
+pub fn assert_mooneye_passes(name: &str) {
+    if let Err(err) = run_mooneye(load_rom(name), 20) {
+        panic!("{} : {}", name, err);
+    }
+}
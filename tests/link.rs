@@ -0,0 +1,52 @@
+// link cable between two TcpLink endpoints in the same process, each one in its own thread
+
+use gbemulator::link::TcpLink;
+use gbemulator::serial::LinkEndpoint;
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+const TRANSFERS: usize = 200;
+const CLOCKS_BETWEEN_TRANSFERS: usize = 97;
+
+#[test]
+fn transfers_in_lockstep() {
+    let (results, receiver) = mpsc::channel();
+    // the system picks a free port, and the host listens before the other side joins
+    let listener = TcpLink::listen(0).expect("Could not listen for the link");
+    let port = listener.local_addr().unwrap().port();
+
+    // the host drives every transfer and gets the number of bytes the other side received so far
+    let host_results = results.clone();
+    thread::spawn(move || {
+        let mut link = TcpLink::accept(&listener).expect("Could not host the link");
+        let mut received = Vec::new();
+        for i in 0..TRANSFERS {
+            for _ in 0..CLOCKS_BETWEEN_TRANSFERS {
+                assert_eq!(link.poll_external(0xFF), None, "the host received a transfer it did not drive");
+            }
+            received.push(link.exchange(i as u8));
+        }
+        host_results.send(("host", received)).unwrap();
+    });
+
+    // the other side only follows the clock of the host
+    thread::spawn(move || {
+        let mut link = TcpLink::join(&port.to_string()).expect("Could not join the host");
+        let mut received = Vec::new();
+        while received.len() < TRANSFERS {
+            if let Some(data) = link.poll_external(received.len() as u8) {
+                received.push(data);
+            }
+        }
+        results.send(("join", received)).unwrap();
+    });
+
+    let expected: Vec<u8> = (0..TRANSFERS).map(|i| i as u8).collect();
+    for _ in 0..2 {
+        let (side, received) = receiver.recv_timeout(Duration::from_secs(30))
+            .expect("The link is stuck, the two sides are waiting for each other");
+        assert_eq!(received, expected, "bytes received by the {} side", side);
+    }
+}
@@ -0,0 +1,299 @@
+// mooneye-gb acceptance tests, which signal their result with LD B,B and the Fibonacci numbers in the registers
+
+mod common;
+
+use common::assert_mooneye_passes;
+
+#[test]
+fn bits_mem_oam() {
+    assert_mooneye_passes("mooneye-gb/acceptance/bits/mem_oam.gb");
+}
+
+#[test]
+fn bits_reg_f() {
+    assert_mooneye_passes("mooneye-gb/acceptance/bits/reg_f.gb");
+}
+
+#[test]
+fn boot_div_dmgabcmgb() {
+    assert_mooneye_passes("mooneye-gb/acceptance/boot_div-dmgABCmgb.gb");
+}
+
+#[test]
+fn boot_regs_dmgabc() {
+    assert_mooneye_passes("mooneye-gb/acceptance/boot_regs-dmgABC.gb");
+}
+
+#[test]
+fn di_timing_gs() {
+    assert_mooneye_passes("mooneye-gb/acceptance/di_timing-GS.gb");
+}
+
+#[test]
+fn div_timing() {
+    assert_mooneye_passes("mooneye-gb/acceptance/div_timing.gb");
+}
+
+#[test]
+fn ei_sequence() {
+    assert_mooneye_passes("mooneye-gb/acceptance/ei_sequence.gb");
+}
+
+#[test]
+fn ei_timing() {
+    assert_mooneye_passes("mooneye-gb/acceptance/ei_timing.gb");
+}
+
+#[test]
+fn halt_ime0_ei() {
+    assert_mooneye_passes("mooneye-gb/acceptance/halt_ime0_ei.gb");
+}
+
+#[test]
+fn halt_ime0_nointr_timing() {
+    assert_mooneye_passes("mooneye-gb/acceptance/halt_ime0_nointr_timing.gb");
+}
+
+#[test]
+fn halt_ime1_timing() {
+    assert_mooneye_passes("mooneye-gb/acceptance/halt_ime1_timing.gb");
+}
+
+#[test]
+fn halt_ime1_timing2_gs() {
+    assert_mooneye_passes("mooneye-gb/acceptance/halt_ime1_timing2-GS.gb");
+}
+
+#[test]
+fn if_ie_registers() {
+    assert_mooneye_passes("mooneye-gb/acceptance/if_ie_registers.gb");
+}
+
+#[test]
+fn instr_daa() {
+    assert_mooneye_passes("mooneye-gb/acceptance/instr/daa.gb");
+}
+
+#[test]
+fn interrupts_ie_push() {
+    assert_mooneye_passes("mooneye-gb/acceptance/interrupts/ie_push.gb");
+}
+
+#[test]
+fn intr_timing() {
+    assert_mooneye_passes("mooneye-gb/acceptance/intr_timing.gb");
+}
+
+#[test]
+fn oam_dma_reg_read() {
+    assert_mooneye_passes("mooneye-gb/acceptance/oam_dma/reg_read.gb");
+}
+
+#[test]
+fn pop_timing() {
+    assert_mooneye_passes("mooneye-gb/acceptance/pop_timing.gb");
+}
+
+#[test]
+fn ppu_intr_1_2_timing_gs() {
+    assert_mooneye_passes("mooneye-gb/acceptance/ppu/intr_1_2_timing-GS.gb");
+}
+
+#[test]
+fn ppu_intr_2_0_timing() {
+    assert_mooneye_passes("mooneye-gb/acceptance/ppu/intr_2_0_timing.gb");
+}
+
+#[test]
+fn ppu_intr_2_mode0_timing() {
+    assert_mooneye_passes("mooneye-gb/acceptance/ppu/intr_2_mode0_timing.gb");
+}
+
+#[test]
+fn ppu_intr_2_mode3_timing() {
+    assert_mooneye_passes("mooneye-gb/acceptance/ppu/intr_2_mode3_timing.gb");
+}
+
+#[test]
+fn ppu_stat_irq_blocking() {
+    assert_mooneye_passes("mooneye-gb/acceptance/ppu/stat_irq_blocking.gb");
+}
+
+#[test]
+fn ppu_vblank_stat_intr_gs() {
+    assert_mooneye_passes("mooneye-gb/acceptance/ppu/vblank_stat_intr-GS.gb");
+}
+
+#[test]
+fn rapid_di_ei() {
+    assert_mooneye_passes("mooneye-gb/acceptance/rapid_di_ei.gb");
+}
+
+#[test]
+fn reti_intr_timing() {
+    assert_mooneye_passes("mooneye-gb/acceptance/reti_intr_timing.gb");
+}
+
+#[test]
+fn serial_boot_sclk_align_dmgabcmgb() {
+    assert_mooneye_passes("mooneye-gb/acceptance/serial/boot_sclk_align-dmgABCmgb.gb");
+}
+
+#[test]
+fn timer_div_write() {
+    assert_mooneye_passes("mooneye-gb/acceptance/timer/div_write.gb");
+}
+
+#[test]
+fn timer_rapid_toggle() {
+    assert_mooneye_passes("mooneye-gb/acceptance/timer/rapid_toggle.gb");
+}
+
+#[test]
+fn timer_tim00() {
+    assert_mooneye_passes("mooneye-gb/acceptance/timer/tim00.gb");
+}
+
+#[test]
+fn timer_tim00_div_trigger() {
+    assert_mooneye_passes("mooneye-gb/acceptance/timer/tim00_div_trigger.gb");
+}
+
+#[test]
+fn timer_tim01() {
+    assert_mooneye_passes("mooneye-gb/acceptance/timer/tim01.gb");
+}
+
+#[test]
+fn timer_tim01_div_trigger() {
+    assert_mooneye_passes("mooneye-gb/acceptance/timer/tim01_div_trigger.gb");
+}
+
+#[test]
+fn timer_tim10() {
+    assert_mooneye_passes("mooneye-gb/acceptance/timer/tim10.gb");
+}
+
+#[test]
+fn timer_tim10_div_trigger() {
+    assert_mooneye_passes("mooneye-gb/acceptance/timer/tim10_div_trigger.gb");
+}
+
+#[test]
+fn timer_tim11() {
+    assert_mooneye_passes("mooneye-gb/acceptance/timer/tim11.gb");
+}
+
+#[test]
+fn timer_tim11_div_trigger() {
+    assert_mooneye_passes("mooneye-gb/acceptance/timer/tim11_div_trigger.gb");
+}
+
+#[test]
+fn timer_tima_reload() {
+    assert_mooneye_passes("mooneye-gb/acceptance/timer/tima_reload.gb");
+}
+
+// these rely on OAM DMA taking 160 M-cycles and blocking the bus, while it is instant here
+
+#[test]
+#[ignore = "OAM DMA is not timed"]
+fn call_cc_timing() {
+    assert_mooneye_passes("mooneye-gb/acceptance/call_cc_timing.gb");
+}
+
+#[test]
+#[ignore = "OAM DMA is not timed"]
+fn call_cc_timing2() {
+    assert_mooneye_passes("mooneye-gb/acceptance/call_cc_timing2.gb");
+}
+
+#[test]
+#[ignore = "OAM DMA is not timed"]
+fn call_timing() {
+    assert_mooneye_passes("mooneye-gb/acceptance/call_timing.gb");
+}
+
+#[test]
+#[ignore = "OAM DMA is not timed"]
+fn call_timing2() {
+    assert_mooneye_passes("mooneye-gb/acceptance/call_timing2.gb");
+}
+
+#[test]
+#[ignore = "OAM DMA is not timed"]
+fn jp_cc_timing() {
+    assert_mooneye_passes("mooneye-gb/acceptance/jp_cc_timing.gb");
+}
+
+#[test]
+#[ignore = "OAM DMA is not timed"]
+fn jp_timing() {
+    assert_mooneye_passes("mooneye-gb/acceptance/jp_timing.gb");
+}
+
+#[test]
+#[ignore = "OAM DMA is not timed"]
+fn ret_cc_timing() {
+    assert_mooneye_passes("mooneye-gb/acceptance/ret_cc_timing.gb");
+}
+
+#[test]
+#[ignore = "OAM DMA is not timed"]
+fn ret_timing() {
+    assert_mooneye_passes("mooneye-gb/acceptance/ret_timing.gb");
+}
+
+#[test]
+#[ignore = "OAM DMA is not timed"]
+fn reti_timing() {
+    assert_mooneye_passes("mooneye-gb/acceptance/reti_timing.gb");
+}
+
+#[test]
+#[ignore = "OAM DMA is not timed"]
+fn rst_timing() {
+    assert_mooneye_passes("mooneye-gb/acceptance/rst_timing.gb");
+}
+
+#[test]
+#[ignore = "OAM DMA is not timed"]
+fn push_timing() {
+    assert_mooneye_passes("mooneye-gb/acceptance/push_timing.gb");
+}
+
+#[test]
+#[ignore = "OAM DMA is not timed"]
+fn add_sp_e_timing() {
+    assert_mooneye_passes("mooneye-gb/acceptance/add_sp_e_timing.gb");
+}
+
+#[test]
+#[ignore = "OAM DMA is not timed"]
+fn ld_hl_sp_e_timing() {
+    assert_mooneye_passes("mooneye-gb/acceptance/ld_hl_sp_e_timing.gb");
+}
+
+#[test]
+#[ignore = "OAM DMA is not timed"]
+fn oam_dma_basic() {
+    assert_mooneye_passes("mooneye-gb/acceptance/oam_dma/basic.gb");
+}
+
+#[test]
+#[ignore = "OAM DMA is not timed"]
+fn oam_dma_restart() {
+    assert_mooneye_passes("mooneye-gb/acceptance/oam_dma_restart.gb");
+}
+
+#[test]
+#[ignore = "OAM DMA is not timed"]
+fn oam_dma_start() {
+    assert_mooneye_passes("mooneye-gb/acceptance/oam_dma_start.gb");
+}
+
+#[test]
+#[ignore = "OAM DMA is not timed"]
+fn oam_dma_timing() {
+    assert_mooneye_passes("mooneye-gb/acceptance/oam_dma_timing.gb");
+}
@@ -0,0 +1,80 @@
+// MBC3 real-time clock in deterministic mode, advancing with emulated clock cycles only
+
+use gbemulator::GameBoy;
+
+use std::fs;
+
+const RAM_ENABLE: u16 = 0x0000;
+const RAM_BANK: u16 = 0x4000;
+const LATCH: u16 = 0x6000;
+const RTC_REGISTER: u16 = 0xA000;
+
+const SECONDS: u8 = 0x08;
+const MINUTES: u8 = 0x09;
+const HOURS: u8 = 0x0A;
+const DAYS_LOW: u8 = 0x0B;
+const DAYS_HIGH: u8 = 0x0C;
+
+const RAM_SIZE: usize = 0x2000;
+const FOOTER_SIZE: usize = 48;
+
+fn new_rtc_gameboy() -> GameBoy {
+    // MBC3+TIMER+RAM+BATTERY cartridge with 8 KiB of RAM, looping on JR -2
+    let mut rom = vec![0; 0x8000];
+    rom[0x100] = 0x18;
+    rom[0x101] = 0xFE;
+    rom[0x147] = 0x10;
+    rom[0x149] = 0x02;
+    let mut gameboy = GameBoy::from_rom(rom).unwrap();
+    gameboy.set_deterministic_rtc(true);
+    gameboy.bus.set_byte(RAM_ENABLE, 0x0A);
+    gameboy
+}
+
+fn set_register(gameboy: &mut GameBoy, register: u8, data: u8) {
+    gameboy.bus.set_byte(RAM_BANK, register);
+    gameboy.bus.set_byte(RTC_REGISTER, data);
+}
+
+fn latched_registers(gameboy: &mut GameBoy) -> [u8; 5] {
+    gameboy.bus.set_byte(LATCH, 0);
+    gameboy.bus.set_byte(LATCH, 1);
+    let mut registers = [0; 5];
+    for (register, value) in (SECONDS..=DAYS_HIGH).zip(registers.iter_mut()) {
+        gameboy.bus.set_byte(RAM_BANK, register);
+        *value = gameboy.bus.fetch_byte(RTC_REGISTER);
+    }
+    registers
+}
+
+#[test]
+fn advances_with_emulated_time_and_saves_footer() {
+    let mut gameboy = new_rtc_gameboy();
+    // 511 days 23:59:58, the last 2 seconds before the day counter overflows
+    set_register(&mut gameboy, DAYS_HIGH, 0x01);
+    set_register(&mut gameboy, DAYS_LOW, 0xFF);
+    set_register(&mut gameboy, HOURS, 23);
+    set_register(&mut gameboy, MINUTES, 59);
+    set_register(&mut gameboy, SECONDS, 58);
+
+    // 300 frames of 70224 clocks are 5.02 seconds at 4194304 Hz
+    for _ in 0..300 {
+        gameboy.run_frame();
+    }
+    let registers = latched_registers(&mut gameboy);
+    assert_eq!(registers, [3, 0, 0, 0, 0x80], "seconds, minutes, hours, days and day carry after 5 seconds");
+
+    // the battery save is the RAM followed by the clock footer
+    let path = std::env::temp_dir().join(format!("gbemulator-rtc-{}.sav", std::process::id()));
+    gameboy.load_battery_save(path.clone()).unwrap();
+    gameboy.flush_battery_save().unwrap();
+    let size = fs::metadata(&path).unwrap().len() as usize;
+
+    let mut restored = new_rtc_gameboy();
+    restored.load_battery_save(path.clone()).unwrap();
+    let restored_registers = latched_registers(&mut restored);
+    fs::remove_file(&path).ok();
+
+    assert_eq!(size, RAM_SIZE + FOOTER_SIZE);
+    assert_eq!(restored_registers, registers, "clock restored from the save footer");
+}